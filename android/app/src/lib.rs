@@ -1,3 +1,4 @@
+use dust_dds_shapes_demo::{app::ShapesDemoApp, qos_profile::QosProfileLibrary};
 use winit::platform::android::activity::AndroidApp;
 
 #[no_mangle]
@@ -15,10 +16,17 @@ fn android_main(app: AndroidApp) {
         default_theme: eframe::Theme::Light,
         ..Default::default()
     };
+    let app = match ShapesDemoApp::new(QosProfileLibrary::default()) {
+        Ok(app) => app,
+        Err(err) => {
+            log::error!("Failure while creating the application: {err}");
+            return;
+        }
+    };
     eframe::run_native(
         "Dust DDS Shapes Demo",
         options,
-        Box::new(|_cc| Box::<dust_dds_shapes_demo::app::ShapesDemoApp>::default()),
+        Box::new(|_cc| Box::new(app)),
     )
     .unwrap_or_else(|err| {
         log::error!("Failure while running EFrame application: {err:?}");
@@ -3,66 +3,429 @@ pub mod shapes_type {
 }
 
 use self::shapes_type::ShapeType;
-use super::shapes_widget::{GuiShape, MovingShapeObject, ShapesWidget};
+use super::{
+    discovery_widget::{format_duration_ms, format_key, DiscoveryWidget},
+    entity_qos::EntityQosWidget,
+    event_log::EventLog,
+    impairment::Impairment,
+    qos_profile::QosProfileLibrary,
+    reader_statistics::{RateMeter, Sparkline},
+    reader_task::ReaderTask,
+    scenario::{
+        ParticipantSettings, ReaderSettings, Scenario, ScenarioWidget, WriterSettings,
+        DEFAULT_POSITION, DEFAULT_WORLD_SIZE,
+    },
+    shapes_widget::{hex_color, GuiShape, MovingShapeObject, ShapesWidget, SHAPE_KINDS},
+    stress_test::StressTestWidget,
+    timeline::{Action, Timeline, TimelineRunner},
+    topic_registry::TopicRegistry,
+};
 use dust_dds::{
-    domain::{
-        domain_participant::DomainParticipant, domain_participant_factory::DomainParticipantFactory,
+    dds_async::{
+        data_reader::DataReaderAsync, data_writer::DataWriterAsync,
+        domain_participant::DomainParticipantAsync,
+        domain_participant_factory::DomainParticipantFactoryAsync, publisher::PublisherAsync,
+        subscriber::SubscriberAsync, topic::TopicAsync,
     },
     infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
         listeners::NoOpListener,
         qos::{DataReaderQos, DataWriterQos, QosKind},
         qos_policy::{
-            HistoryQosPolicy, HistoryQosPolicyKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            HistoryQosPolicy, HistoryQosPolicyKind, LatencyBudgetQosPolicy, PresentationQosPolicy,
+            PresentationQosPolicyAccessScopeKind, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+            TransportPriorityQosPolicy, WriterDataLifecycleQosPolicy,
         },
-        status::NO_STATUS,
-        time::DurationKind,
-    },
-    publication::{data_writer::DataWriter, publisher::Publisher},
-    subscription::{
-        data_reader::DataReader,
-        sample_info::{ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
-        subscriber::Subscriber,
+        status::{SampleRejectedStatus, SampleRejectedStatusKind, NO_STATUS},
+        time::{Duration, DurationKind},
     },
+    subscription::sample_info::InstanceStateKind,
 };
 use eframe::{
     egui::{self},
     epaint::vec2,
 };
-use std::sync::{Arc, Mutex};
+use rand::Rng;
+use std::{
+    collections::HashSet,
+    mem::{discriminant, Discriminant},
+    sync::{
+        atomic::{AtomicBool, AtomicUsize, Ordering},
+        Arc, Mutex, OnceLock,
+    },
+};
 
-struct ShapeWriter {
-    writer: DataWriter<ShapeType>,
+struct ShapeInstance {
     shape: MovingShapeObject,
+    handle: Option<InstanceHandle>,
+}
+
+/// State of the instances of a writer, which only writes them while alive.
+#[derive(Clone, Copy, PartialEq)]
+enum WriterLifecycle {
+    Alive,
+    Unregistered,
+    Disposed,
+}
+
+struct ShapeWriter {
+    participant_id: usize,
+    writer: DataWriterAsync<ShapeType>,
+    instances: Vec<ShapeInstance>,
+    impairment: Impairment<(ShapeType, Option<InstanceHandle>)>,
+    settings: WriterSettings,
+    is_stress_test: bool,
+    /// Publisher of the writer if it groups its changes in coherent sets.
+    coherent_publisher: Option<PublisherAsync>,
+    lifecycle: WriterLifecycle,
 }
 impl ShapeWriter {
-    fn write(&self) {
-        let data = self.shape.gui_shape().as_shape_type();
-        self.writer.write(&data, None).expect("writing failed");
+    /// Samples of all the instances that are due to be written now.
+    fn due_samples(&mut self) -> Vec<(ShapeType, Option<InstanceHandle>)> {
+        if self.lifecycle != WriterLifecycle::Alive {
+            return Vec::new();
+        }
+        let mut samples = Vec::new();
+        for instance in self.instances.iter() {
+            let data = instance.shape.gui_shape().as_shape_type();
+            samples.extend(self.impairment.process((data, instance.handle)));
+        }
+        samples
+    }
+    fn move_instances(&mut self, world_size: egui::Vec2, time_delta: f32) {
+        if self.lifecycle != WriterLifecycle::Alive {
+            return;
+        }
+        for instance in self.instances.iter_mut() {
+            instance.shape.move_within_rect(world_size, time_delta);
+        }
+    }
+    /// Instance state the readers see for the instances of the writer.
+    fn instance_state(&self) -> InstanceStateKind {
+        match self.lifecycle {
+            WriterLifecycle::Alive => InstanceStateKind::Alive,
+            WriterLifecycle::Unregistered if !self.settings.autodispose_unregistered_instances => {
+                InstanceStateKind::NotAliveNoWriters
+            }
+            WriterLifecycle::Unregistered | WriterLifecycle::Disposed => {
+                InstanceStateKind::NotAliveDisposed
+            }
+        }
     }
     fn color(&self) -> String {
-        self.shape.gui_shape().as_shape_type().color.clone()
+        match self.instances.len() {
+            1 => self.settings.color.clone(),
+            n => format!("{} (+{})", self.settings.color, n - 1),
+        }
+    }
+    fn name(&self) -> String {
+        format!("{} {}", self.settings.topic_name, self.color())
+    }
+}
+
+/// Color of the additional instances of a writer, spread over the hue circle.
+fn instance_color(index: usize) -> String {
+    let hue = (index as f32 * 0.618_034) % 1.0;
+    let color = egui::Color32::from(egui::ecolor::Hsva::new(hue, 0.8, 0.9, 1.0));
+    hex_color([color.r(), color.g(), color.b()])
+}
+
+/// Takes the next color of the instance color sequence, which is shared by
+/// all the writers so that their instances can be told apart.
+fn next_instance_color(counter: &AtomicUsize) -> String {
+    instance_color(counter.fetch_add(1, Ordering::Relaxed) + 1)
+}
+
+/// Creates a writer and registers its instances. The writer is deleted again
+/// if registering its instances fails.
+async fn create_shape_writer(
+    participant_id: usize,
+    publisher: &PublisherAsync,
+    topic: &TopicAsync,
+    qos: DataWriterQos,
+    settings: WriterSettings,
+    world_size: egui::Vec2,
+    instance_colors: &AtomicUsize,
+) -> DdsResult<ShapeWriter> {
+    let writer = publisher
+        .create_datawriter(
+            topic,
+            QosKind::Specific(qos),
+            NoOpListener::new(),
+            NO_STATUS,
+        )
+        .await?;
+    let instances = match register_instances(&writer, &settings, world_size, instance_colors).await
+    {
+        Ok(instances) => instances,
+        Err(e) => {
+            // The writer is not in the writer list yet, so it has to be
+            // deleted here to release its topic
+            publisher.delete_datawriter(&writer).await.ok();
+            return Err(e);
+        }
+    };
+    let coherent_publisher = publisher
+        .get_qos()
+        .await?
+        .presentation
+        .coherent_access
+        .then(|| publisher.clone());
+
+    Ok(ShapeWriter {
+        participant_id,
+        writer,
+        instances,
+        impairment: Impairment::new(settings.impairment.clone()),
+        settings,
+        is_stress_test: false,
+        coherent_publisher,
+        lifecycle: WriterLifecycle::Alive,
+    })
+}
+
+/// Registers the instances of a new writer, spawned at the settings position
+/// or at random positions within the world.
+async fn register_instances(
+    writer: &DataWriterAsync<ShapeType>,
+    settings: &WriterSettings,
+    world_size: egui::Vec2,
+    instance_colors: &AtomicUsize,
+) -> DdsResult<Vec<ShapeInstance>> {
+    let mut instances = Vec::new();
+    for (shape_type, velocity) in spawn_instances(settings, world_size, instance_colors) {
+        let handle = writer.register_instance(&shape_type).await?;
+        let shape = MovingShapeObject::new(
+            GuiShape::from_shape_type(settings.topic_name.clone(), &shape_type),
+            velocity,
+            settings.is_pulsing,
+        );
+        instances.push(ShapeInstance { shape, handle });
+    }
+    Ok(instances)
+}
+
+/// Initial sample and velocity of each instance of a writer.
+fn spawn_instances(
+    settings: &WriterSettings,
+    world_size: egui::Vec2,
+    instance_colors: &AtomicUsize,
+) -> Vec<(ShapeType, egui::Vec2)> {
+    let mut rng = rand::thread_rng();
+    let radius = (settings.size / 2.0).min(world_size.min_elem() / 2.0);
+    let mut random_position = || {
+        [
+            rng.gen_range(radius..=world_size.x - radius),
+            rng.gen_range(radius..=world_size.y - radius),
+        ]
+    };
+    let velocity = vec2(settings.velocity[0], settings.velocity[1]);
+    let mut instances = Vec::new();
+    for index in 0..settings.instance_count.max(1) {
+        // Additional instances spawn at random positions with a rotated velocity
+        let (color, position, velocity) = if index == 0 {
+            (
+                settings.color.clone(),
+                settings.position.unwrap_or_else(&mut random_position),
+                velocity,
+            )
+        } else {
+            let angle = index as f32 * std::f32::consts::TAU / settings.instance_count as f32;
+            (
+                next_instance_color(instance_colors),
+                random_position(),
+                egui::emath::Rot2::from_angle(angle) * velocity,
+            )
+        };
+        let shape_type = ShapeType {
+            color,
+            x: position[0] as i32,
+            y: position[1] as i32,
+            shapesize: settings.size as i32,
+        };
+        instances.push((shape_type, velocity));
+    }
+    instances
+}
+
+/// Writers of a stress test are created by a task of their own, since
+/// registering thousands of instances would stall the GUI.
+struct StressTest {
+    participant_id: usize,
+    topic_name: String,
+    stop: Arc<AtomicBool>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl StressTest {
+    /// Stops creating writers and waits for the writer being created.
+    fn stop(self, runtime: &tokio::runtime::Runtime) {
+        self.stop.store(true, Ordering::Relaxed);
+        runtime.block_on(self.task).ok();
+    }
+}
+
+struct ShapeReader {
+    participant_id: usize,
+    reader: DataReaderAsync<ShapeType>,
+    task: ReaderTask,
+    settings: ReaderSettings,
+}
+
+fn rejected_reason(kind: &SampleRejectedStatusKind) -> &'static str {
+    match kind {
+        SampleRejectedStatusKind::NotRejected => "not rejected",
+        SampleRejectedStatusKind::RejectedByInstancesLimit => "instances limit",
+        SampleRejectedStatusKind::RejectedBySamplesLimit => "samples limit",
+        SampleRejectedStatusKind::RejectedBySamplesPerInstanceLimit => "samples per instance limit",
     }
 }
-fn reliability_kind(kind: &ReliabilityQosPolicyKind) -> &'static str {
+
+fn format_rejected(status: &SampleRejectedStatus) -> String {
+    if status.total_count == 0 {
+        return "0".to_string();
+    }
+    format!(
+        "{} ({})",
+        status.total_count,
+        rejected_reason(&status.last_reason)
+    )
+}
+
+pub(crate) fn access_scope_kind(kind: &PresentationQosPolicyAccessScopeKind) -> &'static str {
+    match kind {
+        PresentationQosPolicyAccessScopeKind::Instance => "Instance",
+        PresentationQosPolicyAccessScopeKind::Topic => "Topic",
+    }
+}
+
+/// Describes the presentation of a subscriber, if its readers access the
+/// samples coherently or in order.
+fn presentation_label(presentation: &PresentationQosPolicy) -> Option<String> {
+    let access = match (presentation.coherent_access, presentation.ordered_access) {
+        (false, false) => return None,
+        (true, false) => "Coherent",
+        (false, true) => "Ordered",
+        (true, true) => "Coherent and ordered",
+    };
+    Some(format!(
+        "{} access, {} scope",
+        access,
+        access_scope_kind(&presentation.access_scope)
+    ))
+}
+
+pub(crate) fn reliability_kind(kind: &ReliabilityQosPolicyKind) -> &'static str {
     match kind {
         ReliabilityQosPolicyKind::BestEffort => "Best effort",
         ReliabilityQosPolicyKind::Reliable => "Reliable",
     }
 }
 
+fn reliability_qos(is_reliable: bool) -> ReliabilityQosPolicy {
+    ReliabilityQosPolicy {
+        kind: if is_reliable {
+            ReliabilityQosPolicyKind::Reliable
+        } else {
+            ReliabilityQosPolicyKind::BestEffort
+        },
+        max_blocking_time: DurationKind::Infinite,
+    }
+}
+
+/// Sets the latency budget of a writer or reader, keeping the value of the
+/// QoS profile if the budget is 0.
+fn apply_latency_budget(latency_budget_ms: u32, policy: &mut LatencyBudgetQosPolicy) {
+    if latency_budget_ms == 0 {
+        return;
+    }
+    *policy = LatencyBudgetQosPolicy {
+        duration: duration_ms(latency_budget_ms),
+    };
+}
+
+fn duration_ms(ms: u32) -> DurationKind {
+    DurationKind::Finite(Duration::new((ms / 1000) as i32, (ms % 1000) * 1_000_000))
+}
+
+fn delay_ui(ui: &mut egui::Ui, text: &str, delay_ms: &mut Option<u32>) {
+    ui.horizontal(|ui| {
+        let mut is_set = delay_ms.is_some();
+        ui.checkbox(&mut is_set, text);
+        if is_set {
+            let delay_ms = delay_ms.get_or_insert(1000);
+            ui.add(
+                egui::DragValue::new(delay_ms)
+                    .clamp_range(0..=60_000)
+                    .suffix(" ms"),
+            );
+        } else {
+            *delay_ms = None;
+        }
+    });
+}
+
+fn writer_qos(is_reliable: bool) -> DataWriterQos {
+    DataWriterQos {
+        reliability: reliability_qos(is_reliable),
+        ..Default::default()
+    }
+}
+
+fn reader_qos(is_reliable: bool) -> DataReaderQos {
+    DataReaderQos {
+        reliability: reliability_qos(is_reliable),
+        history: HistoryQosPolicy {
+            kind: HistoryQosPolicyKind::KeepLast(1),
+        },
+        ..Default::default()
+    }
+}
+
+fn qos_profile_combo_box(
+    ui: &mut egui::Ui,
+    id_source: &str,
+    selected_profile: &mut Option<String>,
+    profile_names: &[String],
+) {
+    if profile_names.is_empty() {
+        return;
+    }
+    egui::ComboBox::from_id_source(id_source)
+        .selected_text(selected_profile.as_deref().unwrap_or("No profile"))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(selected_profile, None, "No profile");
+            for name in profile_names {
+                ui.selectable_value(selected_profile, Some(name.clone()), name);
+            }
+        });
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum SelectedEntity {
+    Writer(usize),
+    Reader(usize),
+}
+
 #[derive(Clone)]
 struct PublishWidget {
-    selected_shape: String,
-    is_reliable: bool,
+    settings: WriterSettings,
     selected_color: Option<String>,
+    custom_color: [u8; 3],
+    qos_profile_names: Vec<String>,
 }
 
 impl PublishWidget {
-    fn new(selected_shape: String) -> Self {
+    fn new(selected_shape: String, qos_profile_names: Vec<String>) -> Self {
         Self {
-            selected_shape,
-            is_reliable: false,
+            settings: WriterSettings {
+                topic_name: selected_shape,
+                ..Default::default()
+            },
             selected_color: None,
+            custom_color: [18, 171, 52],
+            qos_profile_names,
         }
     }
     fn add_button(&mut self, ui: &mut egui::Ui, color: &str) {
@@ -82,216 +445,1369 @@ impl egui::Widget for &mut PublishWidget {
         self.add_button(ui, "CYAN");
         self.add_button(ui, "MAGENTA");
         self.add_button(ui, "ORANGE");
-        ui.checkbox(&mut self.is_reliable, "reliable")
+        ui.horizontal(|ui| {
+            egui::color_picker::color_edit_button_srgb(ui, &mut self.custom_color);
+            if ui.button(hex_color(self.custom_color)).clicked() {
+                self.selected_color = Some(hex_color(self.custom_color));
+            }
+        });
+        ui.add(egui::Slider::new(&mut self.settings.size, 5.0..=100.0).text("size"));
+        ui.checkbox(&mut self.settings.is_pulsing, "pulse");
+        ui.add(egui::Slider::new(&mut self.settings.instance_count, 1..=50).text("instances"));
+        ui.add(egui::TextEdit::singleline(&mut self.settings.name).hint_text("name"));
+        // The reliability of a QoS profile replaces the checkbox
+        let response = ui.add_enabled(
+            self.settings.qos_profile.is_none(),
+            egui::Checkbox::new(&mut self.settings.is_reliable, "reliable"),
+        );
+        qos_profile_combo_box(
+            ui,
+            "publish_qos_profile",
+            &mut self.settings.qos_profile,
+            &self.qos_profile_names,
+        );
+        ui.collapsing("Position", |ui| {
+            let mut is_random = self.settings.position.is_none();
+            ui.checkbox(&mut is_random, "random");
+            if is_random {
+                self.settings.position = None;
+            } else {
+                let position = self.settings.position.get_or_insert(DEFAULT_POSITION);
+                ui.horizontal(|ui| {
+                    ui.add(egui::DragValue::new(&mut position[0]).prefix("x: "));
+                    ui.add(egui::DragValue::new(&mut position[1]).prefix("y: "));
+                });
+            }
+        });
+        ui.collapsing("Latency budget and priority", |ui| {
+            ui.add(
+                egui::DragValue::new(&mut self.settings.latency_budget_ms)
+                    .clamp_range(0..=10_000)
+                    .prefix("latency budget: ")
+                    .suffix(" ms"),
+            );
+            ui.add(
+                egui::DragValue::new(&mut self.settings.transport_priority)
+                    .prefix("transport priority: "),
+            );
+        });
+        ui.collapsing("Data lifecycle", |ui| {
+            ui.checkbox(
+                &mut self.settings.autodispose_unregistered_instances,
+                "autodispose unregistered",
+            )
+        });
+        ui.collapsing("Impairment", |ui| ui.add(&mut self.settings.impairment));
+        ui.collapsing("Resource limits", |ui| {
+            ui.add(&mut self.settings.resource_limits)
+        });
+        response
     }
 }
 
 pub struct ShapesDemoApp {
-    participant: DomainParticipant,
-    publisher: Publisher,
-    subscriber: Subscriber,
-    reader_list: Vec<DataReader<ShapeType>>,
+    participants: Vec<ShapeParticipant>,
+    next_participant_id: usize,
+    selected_participant: usize,
+    new_participant_domain_id: i32,
+    new_participant_name: String,
+    reader_list: Vec<ShapeReader>,
     writer_list: Arc<Mutex<Vec<ShapeWriter>>>,
-    time: f64,
-    is_reliable_reader: bool,
+    qos_profiles: QosProfileLibrary,
+    reader_settings: ReaderSettings,
     publish_widget: Option<PublishWidget>,
+    discovery_widget: Option<DiscoveryWidget>,
+    scenario_widget: Option<ScenarioWidget>,
+    timeline_runner: Option<TimelineRunner>,
+    stress_test: Option<StressTest>,
+    stress_test_widget: Option<StressTestWidget>,
+    entity_qos_widget: Option<EntityQosWidget>,
+    publish_meter: RateMeter,
+    drain_meter: RateMeter,
+    last_written_samples: usize,
+    drained_samples: Arc<AtomicUsize>,
+    last_drained_samples: usize,
+    repaint_context: Arc<OnceLock<egui::Context>>,
+    instance_colors: Arc<AtomicUsize>,
+    selected_entity: Option<SelectedEntity>,
+    event_log: EventLog,
+    is_event_log_open: bool,
     planner: Planner,
+    participant_factory: DomainParticipantFactoryAsync,
+    // Declared last, so that the runtime outlives the tasks of the other fields
+    runtime: tokio::runtime::Runtime,
+}
+
+const PUBLISH_RATE_RANGE: std::ops::RangeInclusive<u64> = 5..=500;
+
+/// Time step of the simulation of the shapes, independent of the publish rate
+/// and of the GUI frame rate.
+const SIMULATION_STEP: f32 = 0.01;
+
+type ShapeSample = (ShapeType, Option<InstanceHandle>);
+
+/// Samples written together by the planner. The samples of the writers of a
+/// coherent publisher are written in a single coherent set.
+struct WriteSet {
+    coherent_participant_id: Option<usize>,
+    publisher: Option<PublisherAsync>,
+    writes: Vec<(String, DataWriterAsync<ShapeType>, Vec<ShapeSample>)>,
 }
 
+impl WriteSet {
+    /// Writes the samples and returns the number of samples written.
+    async fn write(&self, write_errors: &mut WriteErrors) -> usize {
+        let coherent_set = self.publisher.as_ref().map(|publisher| {
+            let name = format!(
+                "coherent set of participant {}",
+                self.coherent_participant_id.unwrap_or_default()
+            );
+            (publisher, name)
+        });
+        if let Some((publisher, name)) = &coherent_set {
+            write_errors.report(name, publisher.begin_coherent_changes().await);
+        }
+        let mut written_samples = 0;
+        for (name, writer, samples) in self.writes.iter() {
+            for (data, handle) in samples.iter() {
+                if write_errors.report(name, writer.write(data, *handle).await) {
+                    written_samples += 1;
+                } else {
+                    break;
+                }
+            }
+        }
+        if let Some((publisher, name)) = &coherent_set {
+            write_errors.report(name, publisher.end_coherent_changes().await);
+        }
+        written_samples
+    }
+}
+
+/// Moves the shapes and writes the samples of all the writers at the publish
+/// rate. The task is stopped when the planner is dropped.
 struct Planner {
     writer_list: Arc<Mutex<Vec<ShapeWriter>>>,
     rate: Arc<Mutex<u64>>,
+    world_size: Arc<Mutex<egui::Vec2>>,
+    written_samples: Arc<AtomicUsize>,
+    event_log: EventLog,
+    runtime: tokio::runtime::Handle,
+    shutdown: tokio::sync::watch::Sender<bool>,
+    task: Option<tokio::task::JoinHandle<()>>,
 }
 
 impl Planner {
-    fn new(writer_list: Arc<Mutex<Vec<ShapeWriter>>>) -> Self {
+    fn new(
+        writer_list: Arc<Mutex<Vec<ShapeWriter>>>,
+        event_log: EventLog,
+        runtime: tokio::runtime::Handle,
+    ) -> Self {
         Self {
             writer_list,
             rate: Arc::new(Mutex::new(25)),
+            world_size: Arc::new(Mutex::new(egui::Vec2::from(DEFAULT_WORLD_SIZE))),
+            written_samples: Arc::new(AtomicUsize::new(0)),
+            event_log,
+            runtime,
+            shutdown: tokio::sync::watch::channel(false).0,
+            task: None,
         }
     }
 
     fn start(&mut self) {
         let writer_list_clone = self.writer_list.clone();
         let rate_clone = self.rate.clone();
-        std::thread::spawn(move || loop {
-            let rate = *rate_clone.lock().unwrap();
-            for writer in writer_list_clone.lock().unwrap().iter() {
-                writer.write()
+        let world_size_clone = self.world_size.clone();
+        let written_samples_clone = self.written_samples.clone();
+        let mut write_errors = WriteErrors::new(self.event_log.clone());
+        let mut shutdown = self.shutdown.subscribe();
+        self.task = Some(self.runtime.spawn(async move {
+            let mut last_step = std::time::Instant::now();
+            let mut pending_time = 0.0;
+            loop {
+                let rate = *rate_clone.lock().unwrap();
+                let world_size = *world_size_clone.lock().unwrap();
+                pending_time += last_step.elapsed().as_secs_f32();
+                last_step = std::time::Instant::now();
+                // The samples are collected first, so that the writer list is
+                // not locked while writing
+                let mut write_sets: Vec<WriteSet> = Vec::new();
+                {
+                    let mut writer_list = writer_list_clone.lock().unwrap();
+                    while pending_time >= SIMULATION_STEP {
+                        for writer in writer_list.iter_mut() {
+                            writer.move_instances(world_size, SIMULATION_STEP);
+                        }
+                        pending_time -= SIMULATION_STEP;
+                    }
+                    for writer in writer_list.iter_mut() {
+                        let write = (writer.name(), writer.writer.clone(), writer.due_samples());
+                        let coherent_participant_id = writer
+                            .coherent_publisher
+                            .as_ref()
+                            .map(|_| writer.participant_id);
+                        match write_sets.iter_mut().find(|write_set| {
+                            coherent_participant_id.is_some()
+                                && write_set.coherent_participant_id == coherent_participant_id
+                        }) {
+                            Some(write_set) => write_set.writes.push(write),
+                            None => write_sets.push(WriteSet {
+                                coherent_participant_id,
+                                publisher: writer.coherent_publisher.clone(),
+                                writes: vec![write],
+                            }),
+                        }
+                    }
+                }
+                let write_and_wait = async {
+                    for write_set in write_sets.iter() {
+                        let written_samples = write_set.write(&mut write_errors).await;
+                        written_samples_clone.fetch_add(written_samples, Ordering::Relaxed);
+                    }
+                    tokio::time::sleep(std::time::Duration::from_millis(rate)).await;
+                };
+                tokio::select! {
+                    _ = write_and_wait => (),
+                    _ = shutdown.changed() => break,
+                }
             }
-            std::thread::sleep(std::time::Duration::from_millis(rate));
-        });
+        }));
+    }
+
+    fn stop(&mut self) {
+        self.shutdown.send_replace(true);
+        if let Some(task) = self.task.take() {
+            self.runtime.block_on(task).ok();
+        }
     }
 }
-impl Default for ShapesDemoApp {
-    fn default() -> Self {
-        let domain_id = 0;
-        let participant_factory = DomainParticipantFactory::get_instance();
+
+impl Drop for Planner {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
+
+/// Reports the errors of the periodic writes to the event log. An error is
+/// only reported the first time it happens for each writer and error kind,
+/// until a write of the writer succeeds again, so that the log is not flooded
+/// at the publish rate.
+struct WriteErrors {
+    event_log: EventLog,
+    reported: HashSet<(String, Discriminant<DdsError>)>,
+}
+
+impl WriteErrors {
+    fn new(event_log: EventLog) -> Self {
+        Self {
+            event_log,
+            reported: HashSet::new(),
+        }
+    }
+
+    /// Returns whether the write succeeded.
+    fn report(&mut self, writer: &str, result: DdsResult<()>) -> bool {
+        match result {
+            Ok(()) => {
+                if !self.reported.is_empty() {
+                    self.reported
+                        .retain(|(reported_writer, _)| reported_writer != writer);
+                }
+                true
+            }
+            Err(e) => {
+                if self.reported.insert((writer.to_string(), discriminant(&e))) {
+                    self.event_log
+                        .error(format!("Couldn't write {}: {:?}", writer, e));
+                }
+                false
+            }
+        }
+    }
+}
+
+fn qos_kind<T>(qos: Option<T>) -> QosKind<T> {
+    match qos {
+        Some(qos) => QosKind::Specific(qos),
+        None => QosKind::Default,
+    }
+}
+
+/// Domain participant of the application with the publisher, subscriber and
+/// topics used by its writers and readers.
+struct ShapeParticipant {
+    id: usize,
+    domain_id: i32,
+    /// Name shown in the application. Dust DDS has no EntityName policy, so
+    /// the name isn't sent in discovery.
+    entity_name: String,
+    participant: DomainParticipantAsync,
+    publisher: PublisherAsync,
+    subscriber: SubscriberAsync,
+    topic_registry: TopicRegistry,
+}
+
+impl ShapeParticipant {
+    async fn new(
+        participant_factory: &DomainParticipantFactoryAsync,
+        id: usize,
+        domain_id: i32,
+        entity_name: String,
+        qos_profiles: &QosProfileLibrary,
+    ) -> DdsResult<Self> {
+        let default_profile = qos_profiles.default_profile().cloned().unwrap_or_default();
         let participant = participant_factory
-            .create_participant(domain_id, QosKind::Default, NoOpListener::new(), NO_STATUS)
-            .unwrap();
+            .create_participant(
+                domain_id,
+                qos_kind(default_profile.participant_qos),
+                NoOpListener::new(),
+                NO_STATUS,
+            )
+            .await?;
         let publisher = participant
-            .create_publisher(QosKind::Default, NoOpListener::new(), NO_STATUS)
-            .unwrap();
+            .create_publisher(
+                qos_kind(default_profile.publisher_qos),
+                NoOpListener::new(),
+                NO_STATUS,
+            )
+            .await?;
         let subscriber = participant
-            .create_subscriber(QosKind::Default, NoOpListener::new(), NO_STATUS)
-            .unwrap();
+            .create_subscriber(
+                qos_kind(default_profile.subscriber_qos),
+                NoOpListener::new(),
+                NO_STATUS,
+            )
+            .await?;
+        Ok(Self {
+            id,
+            domain_id,
+            entity_name,
+            participant,
+            publisher,
+            subscriber,
+            topic_registry: TopicRegistry::default(),
+        })
+    }
+
+    /// Replaces the publisher and subscriber by ones with the given
+    /// presentation, since the policy can't be changed once they are enabled.
+    /// The new entities are created first, so that the participant keeps the
+    /// previous ones if that fails.
+    async fn set_presentation(&mut self, presentation: PresentationQosPolicy) -> DdsResult<()> {
+        let mut publisher_qos = self.publisher.get_qos().await?;
+        publisher_qos.presentation = presentation.clone();
+        let mut subscriber_qos = self.subscriber.get_qos().await?;
+        subscriber_qos.presentation = presentation;
+        let publisher = self
+            .participant
+            .create_publisher(
+                QosKind::Specific(publisher_qos),
+                NoOpListener::new(),
+                NO_STATUS,
+            )
+            .await?;
+        let subscriber = match self
+            .participant
+            .create_subscriber(
+                QosKind::Specific(subscriber_qos),
+                NoOpListener::new(),
+                NO_STATUS,
+            )
+            .await
+        {
+            Ok(subscriber) => subscriber,
+            Err(e) => {
+                self.participant.delete_publisher(&publisher).await.ok();
+                return Err(e);
+            }
+        };
+        let previous_publisher = std::mem::replace(&mut self.publisher, publisher);
+        let previous_subscriber = std::mem::replace(&mut self.subscriber, subscriber);
+        self.participant
+            .delete_publisher(&previous_publisher)
+            .await?;
+        self.participant
+            .delete_subscriber(&previous_subscriber)
+            .await
+    }
+
+    fn name(&self) -> String {
+        if self.entity_name.is_empty() {
+            format!("Participant {} (domain {})", self.id, self.domain_id)
+        } else {
+            format!("{} (domain {})", self.entity_name, self.domain_id)
+        }
+    }
+
+    async fn delete(&self, participant_factory: &DomainParticipantFactoryAsync) -> DdsResult<()> {
+        self.participant.delete_contained_entities().await?;
+        participant_factory
+            .delete_participant(&self.participant)
+            .await
+    }
+}
+
+fn participant_name(participants: &[ShapeParticipant], participant_id: usize) -> String {
+    participants
+        .iter()
+        .find(|p| p.id == participant_id)
+        .map(ShapeParticipant::name)
+        .unwrap_or_default()
+}
+
+impl ShapesDemoApp {
+    pub fn new(qos_profiles: QosProfileLibrary) -> Result<Self, String> {
+        let runtime = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .thread_stack_size(4 * 1024 * 1024)
+            .build()
+            .map_err(|e| format!("Couldn't create the runtime: {}", e))?;
+        let participant_factory = DomainParticipantFactoryAsync::new(runtime.handle().clone());
+        let participant = runtime
+            .block_on(ShapeParticipant::new(
+                &participant_factory,
+                1,
+                0,
+                String::new(),
+                &qos_profiles,
+            ))
+            .map_err(|e| format!("Couldn't create the domain participant: {:?}", e))?;
 
         let writer_list = Arc::new(Mutex::new(Vec::new()));
-        let mut planner = Planner::new(writer_list.clone());
+        let event_log = EventLog::default();
+        let mut planner = Planner::new(
+            writer_list.clone(),
+            event_log.clone(),
+            runtime.handle().clone(),
+        );
         planner.start();
 
-        Self {
-            participant,
-            publisher,
-            subscriber,
+        Ok(Self {
+            selected_participant: participant.id,
+            participants: vec![participant],
+            next_participant_id: 2,
+            new_participant_domain_id: 0,
+            new_participant_name: String::new(),
             reader_list: vec![],
             writer_list,
-            time: 0.0,
-            is_reliable_reader: false,
+            qos_profiles,
+            reader_settings: ReaderSettings::default(),
             publish_widget: None,
+            discovery_widget: None,
+            scenario_widget: None,
+            timeline_runner: None,
+            stress_test: None,
+            stress_test_widget: None,
+            entity_qos_widget: None,
+            publish_meter: RateMeter::default(),
+            drain_meter: RateMeter::default(),
+            last_written_samples: 0,
+            drained_samples: Arc::new(AtomicUsize::new(0)),
+            last_drained_samples: 0,
+            repaint_context: Arc::new(OnceLock::new()),
+            instance_colors: Arc::new(AtomicUsize::new(0)),
+            selected_entity: None,
+            event_log,
+            is_event_log_open: false,
             planner,
+            participant_factory,
+            runtime,
+        })
+    }
+
+    fn participant(&self, participant_id: usize) -> DdsResult<&ShapeParticipant> {
+        self.participants
+            .iter()
+            .find(|p| p.id == participant_id)
+            .ok_or(DdsError::AlreadyDeleted)
+    }
+
+    /// Creates a participant and selects it. Returns its id if it was created.
+    fn add_participant(&mut self, domain_id: i32, entity_name: String) -> Option<usize> {
+        let id = self.next_participant_id;
+        self.next_participant_id += 1;
+        let result = self.runtime.block_on(ShapeParticipant::new(
+            &self.participant_factory,
+            id,
+            domain_id,
+            entity_name,
+            &self.qos_profiles,
+        ));
+        match result {
+            Ok(participant) => {
+                self.event_log
+                    .info(format!("Created {}", participant.name()));
+                self.selected_participant = participant.id;
+                self.participants.push(participant);
+                Some(id)
+            }
+            Err(e) => {
+                self.event_log.error(format!(
+                    "Couldn't create participant on domain {}: {:?}",
+                    domain_id, e
+                ));
+                None
+            }
         }
     }
-}
 
-impl ShapesDemoApp {
-    fn create_writer(&mut self, shape_kind: String, color: &str, is_reliable: bool) {
-        let topic_name = shape_kind.as_str();
+    /// Deletes the participant together with its writers and readers.
+    fn remove_participant(&mut self, participant_id: usize) {
+        self.delete_endpoints(Some(participant_id));
+        let Some(index) = self
+            .participants
+            .iter()
+            .position(|p| p.id == participant_id)
+        else {
+            return;
+        };
+        let participant = self.participants.remove(index);
+        match self
+            .runtime
+            .block_on(participant.delete(&self.participant_factory))
+        {
+            Ok(()) => self
+                .event_log
+                .info(format!("Deleted {}", participant.name())),
+            Err(e) => {
+                self.event_log
+                    .error(format!("Couldn't delete {}: {:?}", participant.name(), e))
+            }
+        }
+        if self.selected_participant == participant_id {
+            if let Some(first) = self.participants.first() {
+                self.selected_participant = first.id;
+            }
+        }
+        self.discovery_widget = None;
+        self.entity_qos_widget = None;
+    }
 
-        let topic = self
-            .participant
-            .create_topic::<ShapeType>(
-                topic_name,
-                "ShapeType",
-                QosKind::Default,
-                NoOpListener::new(),
-                NO_STATUS,
-            )
-            .unwrap();
-        let qos = if is_reliable {
-            DataWriterQos {
-                reliability: ReliabilityQosPolicy {
-                    kind: ReliabilityQosPolicyKind::Reliable,
-                    max_blocking_time: DurationKind::Infinite,
-                },
-                ..Default::default()
+    /// Creates a writer on the selected participant.
+    fn create_writer(&mut self, settings: WriterSettings) {
+        let participant_id = self.selected_participant;
+        let topic_name = settings.topic_name.clone();
+        let name = format!("{} {}", settings.topic_name, settings.color);
+        let qos = self.datawriter_qos(&settings);
+        let world_size = *self.planner.world_size.lock().unwrap();
+        let result = self
+            .create_topic(participant_id, &settings.topic_name)
+            .and_then(|topic| {
+                self.runtime.block_on(create_shape_writer(
+                    participant_id,
+                    &self.participant(participant_id)?.publisher,
+                    &topic,
+                    qos,
+                    settings,
+                    world_size,
+                    &self.instance_colors,
+                ))
+            });
+        match result {
+            Ok(shape_writer) => {
+                self.writer_list.lock().unwrap().push(shape_writer);
+                self.event_log.info(format!("Created writer {}", name));
             }
-        } else {
-            DataWriterQos {
-                reliability: ReliabilityQosPolicy {
-                    kind: ReliabilityQosPolicyKind::BestEffort,
-                    max_blocking_time: DurationKind::Infinite,
-                },
-                ..Default::default()
+            Err(e) => {
+                self.event_log
+                    .error(format!("Couldn't create writer {}: {:?}", name, e));
+                self.delete_unused_topic(participant_id, &topic_name);
             }
+        }
+    }
+
+    fn datawriter_qos(&self, settings: &WriterSettings) -> DataWriterQos {
+        let mut qos = settings
+            .qos_profile
+            .as_deref()
+            .and_then(|name| self.qos_profiles.datawriter_qos(name))
+            .unwrap_or_else(|| writer_qos(settings.is_reliable));
+        settings.resource_limits.apply(&mut qos.resource_limits);
+        apply_latency_budget(settings.latency_budget_ms, &mut qos.latency_budget);
+        if settings.transport_priority != 0 {
+            qos.transport_priority = TransportPriorityQosPolicy {
+                value: settings.transport_priority,
+            };
+        }
+        qos.writer_data_lifecycle = WriterDataLifecycleQosPolicy {
+            autodispose_unregistered_instances: settings.autodispose_unregistered_instances,
         };
-        let writer = self
-            .publisher
-            .create_datawriter(
-                &topic,
-                QosKind::Specific(qos),
-                NoOpListener::new(),
-                NO_STATUS,
-            )
-            .unwrap();
-
-        let velocity = vec2(30.0, 20.0);
-        let shape_type = &ShapeType {
-            color: color.to_string(),
-            x: 100,
-            y: 80,
-            shapesize: 30,
+        qos
+    }
+
+    fn create_topic(&mut self, participant_id: usize, topic_name: &str) -> DdsResult<TopicAsync> {
+        let shape_participant = self
+            .participants
+            .iter_mut()
+            .find(|p| p.id == participant_id)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        self.runtime.block_on(
+            shape_participant
+                .topic_registry
+                .get_or_create(&shape_participant.participant, topic_name),
+        )
+    }
+
+    /// Starts a stress test on the selected participant.
+    fn start_stress_test(&mut self, topic_name: &str, writer_count: usize, instance_count: usize) {
+        self.stop_stress_test();
+        let participant_id = self.selected_participant;
+        let publisher = match self.participant(participant_id) {
+            Ok(shape_participant) => shape_participant.publisher.clone(),
+            Err(_) => return,
         };
+        let topic = match self.create_topic(participant_id, topic_name) {
+            Ok(topic) => topic,
+            Err(e) => {
+                self.event_log
+                    .error(format!("Couldn't create topic {}: {:?}", topic_name, e));
+                return;
+            }
+        };
+        let mut rng = rand::thread_rng();
+        let writer_settings: Vec<_> = (0..writer_count)
+            .map(|_| {
+                let angle = rng.gen_range(0.0..std::f32::consts::TAU);
+                let velocity = egui::emath::Rot2::from_angle(angle) * vec2(30.0, 20.0);
+                WriterSettings {
+                    topic_name: topic_name.to_string(),
+                    color: next_instance_color(&self.instance_colors),
+                    position: None,
+                    velocity: velocity.into(),
+                    instance_count,
+                    ..Default::default()
+                }
+            })
+            .collect();
+        let qos = self.datawriter_qos(&WriterSettings::default());
+
+        let stop = Arc::new(AtomicBool::new(false));
+        let stop_clone = stop.clone();
+        let writer_list = self.writer_list.clone();
+        let instance_colors = self.instance_colors.clone();
+        let event_log = self.event_log.clone();
+        let world_size = *self.planner.world_size.lock().unwrap();
+        let task = self.runtime.spawn(async move {
+            for settings in writer_settings {
+                if stop_clone.load(Ordering::Relaxed) {
+                    break;
+                }
+                let result = create_shape_writer(
+                    participant_id,
+                    &publisher,
+                    &topic,
+                    qos.clone(),
+                    settings,
+                    world_size,
+                    &instance_colors,
+                )
+                .await;
+                match result {
+                    Ok(mut shape_writer) => {
+                        shape_writer.is_stress_test = true;
+                        writer_list.lock().unwrap().push(shape_writer);
+                    }
+                    Err(e) => {
+                        event_log.error(format!("Couldn't create stress test writer: {:?}", e));
+                        break;
+                    }
+                }
+            }
+        });
+        self.stress_test = Some(StressTest {
+            participant_id,
+            topic_name: topic_name.to_string(),
+            stop,
+            task,
+        });
+    }
+
+    fn stop_stress_test(&mut self) {
+        let Some(stress_test) = self.stress_test.take() else {
+            return;
+        };
+        let participant_id = stress_test.participant_id;
+        let topic_name = stress_test.topic_name.clone();
+        // The writers it created are deleted below, also if it panicked
+        stress_test.stop(&self.runtime);
 
-        let shape =
-            MovingShapeObject::new(GuiShape::from_shape_type(shape_kind, shape_type), velocity);
+        let stress_test_writers: Vec<ShapeWriter> = {
+            let mut writer_list = self.writer_list.lock().unwrap();
+            let (stress_test_writers, writers) = writer_list
+                .drain(..)
+                .partition(|shape_writer| shape_writer.is_stress_test);
+            *writer_list = writers;
+            stress_test_writers
+        };
+        // The stress test writers are not logged one by one, there may be
+        // thousands of them
+        let writer_count = stress_test_writers.len();
+        if let Ok(shape_participant) = self.participant(participant_id) {
+            self.runtime.block_on(async {
+                for shape_writer in stress_test_writers {
+                    if let Err(e) = shape_participant
+                        .publisher
+                        .delete_datawriter(&shape_writer.writer)
+                        .await
+                    {
+                        self.event_log
+                            .error(format!("Couldn't delete stress test writer: {:?}", e));
+                    }
+                }
+            });
+        }
+        self.event_log
+            .info(format!("Deleted {} stress test writers", writer_count));
+        self.delete_unused_topic(participant_id, &topic_name);
+        self.selected_entity = None;
+    }
 
-        let shape_writer = ShapeWriter { writer, shape };
-        self.writer_list.lock().unwrap().push(shape_writer);
+    /// Creates a reader on the selected participant.
+    fn create_reader(&mut self, settings: ReaderSettings) {
+        let participant_id = self.selected_participant;
+        let name = settings.topic_name.clone();
+        match self.create_shape_reader(settings) {
+            Ok(shape_reader) => {
+                self.reader_list.push(shape_reader);
+                self.event_log.info(format!("Created reader {}", name));
+            }
+            Err(e) => {
+                self.event_log
+                    .error(format!("Couldn't create reader {}: {:?}", name, e));
+                self.delete_unused_topic(participant_id, &name);
+            }
+        }
     }
 
-    fn create_reader(&mut self, topic_name: &str, is_reliable: bool) {
-        let topic = self
-            .participant
-            .create_topic::<ShapeType>(
-                topic_name,
-                "ShapeType",
-                QosKind::Default,
-                NoOpListener::new(),
-                NO_STATUS,
+    /// Creates a reader and starts its task. The reader is deleted again if
+    /// its task fails to start.
+    fn create_shape_reader(&mut self, settings: ReaderSettings) -> DdsResult<ShapeReader> {
+        let mut qos = settings
+            .qos_profile
+            .as_deref()
+            .and_then(|name| self.qos_profiles.datareader_qos(name))
+            .unwrap_or_else(|| reader_qos(settings.is_reliable));
+        settings.resource_limits.apply(&mut qos.resource_limits);
+        apply_latency_budget(settings.latency_budget_ms, &mut qos.latency_budget);
+        if let Some(delay_ms) = settings.autopurge_nowriter_samples_delay_ms {
+            qos.reader_data_lifecycle.autopurge_nowriter_samples_delay = duration_ms(delay_ms);
+        }
+        if let Some(delay_ms) = settings.autopurge_disposed_samples_delay_ms {
+            qos.reader_data_lifecycle.autopurge_disposed_samples_delay = duration_ms(delay_ms);
+        }
+        let participant_id = self.selected_participant;
+        let topic = self.create_topic(participant_id, &settings.topic_name)?;
+        let subscriber = &self.participant(participant_id)?.subscriber;
+        self.runtime.block_on(async {
+            let reader = subscriber
+                .create_datareader(
+                    &topic,
+                    QosKind::Specific(qos),
+                    NoOpListener::new(),
+                    NO_STATUS,
+                )
+                .await?;
+            let task = match ReaderTask::start(
+                reader.clone(),
+                self.drained_samples.clone(),
+                self.repaint_context.clone(),
             )
-            .unwrap();
-        let qos = if is_reliable {
-            DataReaderQos {
-                reliability: ReliabilityQosPolicy {
-                    kind: ReliabilityQosPolicyKind::Reliable,
-                    max_blocking_time: DurationKind::Infinite,
-                },
-                history: HistoryQosPolicy {
-                    kind: HistoryQosPolicyKind::KeepLast(1),
-                },
-                ..Default::default()
+            .await
+            {
+                Ok(task) => task,
+                Err(e) => {
+                    // The reader is not in the reader list yet, so it has to be
+                    // deleted here to release its topic
+                    subscriber.delete_datareader(&reader).await.ok();
+                    return Err(e);
+                }
+            };
+            Ok(ShapeReader {
+                participant_id,
+                reader,
+                task,
+                settings,
+            })
+        })
+    }
+
+    /// Unregisters or disposes all the instances of a writer, or resumes
+    /// writing them. The writer stops writing before its instances change and
+    /// the samples delayed by its impairment are dropped, so that no stale
+    /// sample brings the instances back to life.
+    fn set_writer_lifecycle(&self, index: usize, lifecycle: WriterLifecycle) {
+        let (name, writer, instances) = {
+            let mut writer_list = self.writer_list.lock().unwrap();
+            let Some(shape_writer) = writer_list.get_mut(index) else {
+                return;
+            };
+            shape_writer.impairment.clear();
+            if lifecycle == WriterLifecycle::Alive {
+                // register_instance is not implemented by Dust DDS, the
+                // instances are registered again by their next write
+                for instance in shape_writer.instances.iter_mut() {
+                    instance.handle = None;
+                }
+                shape_writer.lifecycle = lifecycle;
+                return;
             }
-        } else {
-            DataReaderQos {
-                reliability: ReliabilityQosPolicy {
-                    kind: ReliabilityQosPolicyKind::BestEffort,
-                    max_blocking_time: DurationKind::Infinite,
-                },
-                history: HistoryQosPolicy {
-                    kind: HistoryQosPolicyKind::KeepLast(1),
-                },
-                ..Default::default()
+            shape_writer.lifecycle = lifecycle;
+            let instances: Vec<_> = shape_writer
+                .instances
+                .iter()
+                .map(|instance| (instance.shape.gui_shape().as_shape_type(), instance.handle))
+                .collect();
+            (shape_writer.name(), shape_writer.writer.clone(), instances)
+        };
+        let result = self.runtime.block_on(async {
+            for (data, handle) in instances.iter() {
+                if lifecycle == WriterLifecycle::Disposed {
+                    writer.dispose(data, *handle).await?;
+                } else {
+                    writer.unregister_instance(data, *handle).await?;
+                }
             }
+            DdsResult::Ok(())
+        });
+        if let Err(e) = result {
+            self.event_log.error(format!(
+                "Couldn't change the instances of {}: {:?}",
+                name, e
+            ));
+        }
+    }
+
+    fn delete_writer(&mut self, index: usize) {
+        let shape_writer = self.writer_list.lock().unwrap().remove(index);
+        let participant_id = shape_writer.participant_id;
+        let topic_name = shape_writer.settings.topic_name.clone();
+        self.runtime
+            .block_on(self.delete_shape_writer(shape_writer));
+        self.delete_unused_topic(participant_id, &topic_name);
+        self.selected_entity = None;
+    }
+
+    async fn delete_shape_writer(&self, shape_writer: ShapeWriter) {
+        let name = shape_writer.name();
+        let Ok(shape_participant) = self.participant(shape_writer.participant_id) else {
+            return;
         };
-        let reader = self
+        match shape_participant
+            .publisher
+            .delete_datawriter(&shape_writer.writer)
+            .await
+        {
+            Ok(()) => self.event_log.info(format!("Deleted writer {}", name)),
+            Err(e) => self
+                .event_log
+                .error(format!("Couldn't delete writer {}: {:?}", name, e)),
+        }
+    }
+
+    fn delete_reader(&mut self, index: usize) {
+        let shape_reader = self.reader_list.remove(index);
+        let participant_id = shape_reader.participant_id;
+        let topic_name = shape_reader.settings.topic_name.clone();
+        self.runtime
+            .block_on(self.delete_shape_reader(shape_reader));
+        self.delete_unused_topic(participant_id, &topic_name);
+        self.selected_entity = None;
+    }
+
+    async fn delete_shape_reader(&self, shape_reader: ShapeReader) {
+        let name = shape_reader.settings.topic_name.clone();
+        let Ok(shape_participant) = self.participant(shape_reader.participant_id) else {
+            return;
+        };
+        match shape_participant
             .subscriber
-            .create_datareader(
-                &topic,
-                QosKind::Specific(qos),
-                NoOpListener::new(),
-                NO_STATUS,
-            )
-            .unwrap();
-        self.reader_list.push(reader);
+            .delete_datareader(&shape_reader.reader)
+            .await
+        {
+            Ok(()) => self.event_log.info(format!("Deleted reader {}", name)),
+            Err(e) => self
+                .event_log
+                .error(format!("Couldn't delete reader {}: {:?}", name, e)),
+        }
     }
 
-    fn menu_panel(&mut self, ui: &mut egui::Ui) {
-        ui.heading("Publish");
-        if ui.button("Square").clicked() {
-            self.publish_widget = Some(PublishWidget::new("Square".to_string()));
+    /// Deletes the topic once no writer or reader of the participant uses it.
+    fn delete_unused_topic(&mut self, participant_id: usize, topic_name: &str) {
+        let is_used = self.writer_list.lock().unwrap().iter().any(|shape_writer| {
+            shape_writer.participant_id == participant_id
+                && shape_writer.settings.topic_name == topic_name
+        }) || self.reader_list.iter().any(|shape_reader| {
+            shape_reader.participant_id == participant_id
+                && shape_reader.settings.topic_name == topic_name
+        });
+        if is_used {
+            return;
+        }
+        let Some(shape_participant) = self
+            .participants
+            .iter_mut()
+            .find(|p| p.id == participant_id)
+        else {
+            return;
         };
-        if ui.button("Circle").clicked() {
-            self.publish_widget = Some(PublishWidget::new("Circle".to_string()));
+        let result = self.runtime.block_on(
+            shape_participant
+                .topic_registry
+                .delete(&shape_participant.participant, topic_name),
+        );
+        if let Err(e) = result {
+            self.event_log
+                .error(format!("Couldn't delete topic {}: {:?}", topic_name, e));
+        }
+    }
+
+    /// Deletes the writers and readers of the participant, or of all the
+    /// participants if none is given.
+    fn delete_endpoints(&mut self, participant_id: Option<usize>) {
+        let is_deleted =
+            |id: usize| participant_id.is_none_or(|participant_id| participant_id == id);
+        if self
+            .stress_test
+            .as_ref()
+            .is_some_and(|stress_test| is_deleted(stress_test.participant_id))
+        {
+            self.stop_stress_test();
+        }
+        let writers: Vec<_> = {
+            let mut writer_list = self.writer_list.lock().unwrap();
+            let (writers, kept) = writer_list
+                .drain(..)
+                .partition(|shape_writer| is_deleted(shape_writer.participant_id));
+            *writer_list = kept;
+            writers
         };
-        if ui.button("Triangle").clicked() {
-            self.publish_widget = Some(PublishWidget::new("Triangle".to_string()));
+        let (readers, kept): (Vec<_>, Vec<_>) = std::mem::take(&mut self.reader_list)
+            .into_iter()
+            .partition(|shape_reader| is_deleted(shape_reader.participant_id));
+        self.reader_list = kept;
+        let mut topics: Vec<_> = writers
+            .iter()
+            .map(|shape_writer| {
+                (
+                    shape_writer.participant_id,
+                    shape_writer.settings.topic_name.clone(),
+                )
+            })
+            .chain(readers.iter().map(|shape_reader| {
+                (
+                    shape_reader.participant_id,
+                    shape_reader.settings.topic_name.clone(),
+                )
+            }))
+            .collect();
+        topics.sort();
+        topics.dedup();
+        self.runtime.block_on(async {
+            for shape_writer in writers {
+                self.delete_shape_writer(shape_writer).await;
+            }
+            for shape_reader in readers {
+                self.delete_shape_reader(shape_reader).await;
+            }
+        });
+        for (participant_id, topic_name) in topics {
+            self.delete_unused_topic(participant_id, &topic_name);
+        }
+        self.selected_entity = None;
+    }
+
+    fn scenario(&self) -> Scenario {
+        let writer_list = self.writer_list.lock().unwrap();
+        Scenario {
+            publish_rate_ms: *self.planner.rate.lock().unwrap(),
+            world_size: (*self.planner.world_size.lock().unwrap()).into(),
+            participants: self
+                .participants
+                .iter()
+                .map(|shape_participant| ParticipantSettings {
+                    domain_id: shape_participant.domain_id,
+                    name: shape_participant.entity_name.clone(),
+                    writers: writer_list
+                        .iter()
+                        .filter(|shape_writer| {
+                            shape_writer.participant_id == shape_participant.id
+                                && !shape_writer.is_stress_test
+                        })
+                        .map(|shape_writer| shape_writer.settings.clone())
+                        .collect(),
+                    readers: self
+                        .reader_list
+                        .iter()
+                        .filter(|shape_reader| shape_reader.participant_id == shape_participant.id)
+                        .map(|shape_reader| shape_reader.settings.clone())
+                        .collect(),
+                })
+                .collect(),
+        }
+    }
+
+    /// Replaces the participants of the application by the ones of the
+    /// scenario. The current participants are kept if none of the scenario
+    /// can be created.
+    pub fn load_scenario(&mut self, scenario: Scenario) {
+        self.delete_endpoints(None);
+        self.set_publish_rate(scenario.publish_rate_ms);
+        *self.planner.world_size.lock().unwrap() = egui::Vec2::from(scenario.world_size);
+        let previous_participants: Vec<_> = self.participants.iter().map(|p| p.id).collect();
+        for participant_settings in scenario.participants {
+            if self
+                .add_participant(participant_settings.domain_id, participant_settings.name)
+                .is_none()
+            {
+                continue;
+            }
+            for settings in participant_settings.writers {
+                self.create_writer(settings);
+            }
+            for settings in participant_settings.readers {
+                self.create_reader(settings);
+            }
+        }
+        if self.participants.len() > previous_participants.len() {
+            for participant_id in previous_participants {
+                self.remove_participant(participant_id);
+            }
+        }
+        self.selected_participant = self.participants[0].id;
+    }
+
+    fn matched_endpoints(&self, entity: SelectedEntity) -> Vec<String> {
+        match entity {
+            SelectedEntity::Writer(index) => {
+                let Some(writer) = self
+                    .writer_list
+                    .lock()
+                    .unwrap()
+                    .get(index)
+                    .map(|shape_writer| shape_writer.writer.clone())
+                else {
+                    return Vec::new();
+                };
+                self.runtime.block_on(async {
+                    let mut matched_endpoints = Vec::new();
+                    for handle in writer.get_matched_subscriptions().await.unwrap_or_default() {
+                        if let Ok(data) = writer.get_matched_subscription_data(handle).await {
+                            matched_endpoints.push(format_key(data.key()));
+                        }
+                    }
+                    matched_endpoints
+                })
+            }
+            SelectedEntity::Reader(index) => {
+                let Some(ShapeReader { reader, .. }) = self.reader_list.get(index) else {
+                    return Vec::new();
+                };
+                self.runtime.block_on(async {
+                    let mut matched_endpoints = Vec::new();
+                    for handle in reader.get_matched_publications().await.unwrap_or_default() {
+                        if let Ok(data) = reader.get_matched_publication_data(handle).await {
+                            matched_endpoints.push(format_key(data.key()));
+                        }
+                    }
+                    matched_endpoints
+                })
+            }
+        }
+    }
+
+    /// Sets the publish rate in milliseconds, clamped to the range of the
+    /// slider since a rate of 0 would make the planner spin.
+    fn set_publish_rate(&self, rate: u64) {
+        *self.planner.rate.lock().unwrap() =
+            rate.clamp(*PUBLISH_RATE_RANGE.start(), *PUBLISH_RATE_RANGE.end());
+    }
+
+    pub fn run_timeline(&mut self, timeline: Timeline) {
+        self.timeline_runner = Some(TimelineRunner::new(timeline));
+    }
+
+    fn apply_action(&mut self, action: Action) {
+        match action {
+            Action::CreateWriter(settings) => self.create_writer(settings),
+            Action::CreateReader(settings) => self.create_reader(settings),
+            Action::DeleteWriter { topic_name, color } => {
+                let index = self.writer_list.lock().unwrap().iter().rposition(|w| {
+                    topic_name
+                        .as_ref()
+                        .is_none_or(|t| *t == w.settings.topic_name)
+                        && color.as_ref().is_none_or(|c| *c == w.settings.color)
+                });
+                if let Some(index) = index {
+                    self.delete_writer(index);
+                }
+            }
+            Action::DeleteReader { topic_name } => {
+                let index = self.reader_list.iter().rposition(|r| {
+                    topic_name
+                        .as_ref()
+                        .is_none_or(|t| *t == r.settings.topic_name)
+                });
+                if let Some(index) = index {
+                    self.delete_reader(index);
+                }
+            }
+            Action::SetPublishRate(rate) => self.set_publish_rate(rate),
+        }
+    }
+
+    /// Dialog to edit the QoS of the selected participant.
+    fn entity_qos_widget(&self) -> DdsResult<EntityQosWidget> {
+        let shape_participant = self.participant(self.selected_participant)?;
+        self.runtime.block_on(async {
+            Ok(EntityQosWidget::new(
+                &shape_participant
+                    .participant
+                    .get_qos()
+                    .await?
+                    .user_data
+                    .value,
+                &shape_participant
+                    .publisher
+                    .get_qos()
+                    .await?
+                    .group_data
+                    .value,
+                &shape_participant
+                    .subscriber
+                    .get_qos()
+                    .await?
+                    .group_data
+                    .value,
+                shape_participant.publisher.get_qos().await?.presentation,
+                shape_participant.topic_registry.qos().clone(),
+            ))
+        })
+    }
+
+    fn apply_entity_qos(&mut self, entity_qos_widget: &EntityQosWidget) -> DdsResult<()> {
+        let participant_id = self.selected_participant;
+        let has_endpoints = self
+            .writer_list
+            .lock()
+            .unwrap()
+            .iter()
+            .any(|shape_writer| shape_writer.participant_id == participant_id)
+            || self
+                .reader_list
+                .iter()
+                .any(|shape_reader| shape_reader.participant_id == participant_id);
+        let shape_participant = self
+            .participants
+            .iter_mut()
+            .find(|p| p.id == participant_id)
+            .ok_or(DdsError::AlreadyDeleted)?;
+        self.runtime.block_on(async {
+            if shape_participant.publisher.get_qos().await?.presentation
+                != entity_qos_widget.presentation
+            {
+                if has_endpoints {
+                    return Err(DdsError::PreconditionNotMet(
+                        "The presentation can only be changed without writers and readers"
+                            .to_string(),
+                    ));
+                }
+                shape_participant
+                    .set_presentation(entity_qos_widget.presentation.clone())
+                    .await?;
+            }
+
+            let mut participant_qos = shape_participant.participant.get_qos().await?;
+            participant_qos.user_data.value = entity_qos_widget.user_data.as_bytes().to_vec();
+            shape_participant
+                .participant
+                .set_qos(QosKind::Specific(participant_qos))
+                .await?;
+
+            let mut publisher_qos = shape_participant.publisher.get_qos().await?;
+            publisher_qos.group_data.value =
+                entity_qos_widget.publisher_group_data.as_bytes().to_vec();
+            shape_participant
+                .publisher
+                .set_qos(QosKind::Specific(publisher_qos))
+                .await?;
+
+            let mut subscriber_qos = shape_participant.subscriber.get_qos().await?;
+            subscriber_qos.group_data.value =
+                entity_qos_widget.subscriber_group_data.as_bytes().to_vec();
+            shape_participant
+                .subscriber
+                .set_qos(QosKind::Specific(subscriber_qos))
+                .await?;
+
+            let mut topic_qos = entity_qos_widget.topic_qos.clone();
+            topic_qos.topic_data.value = entity_qos_widget.topic_data.as_bytes().to_vec();
+            shape_participant.topic_registry.set_qos(topic_qos).await
+        })
+    }
+
+    fn create_reader_with_topic(&mut self, topic_name: &str) {
+        self.create_reader(ReaderSettings {
+            topic_name: topic_name.to_string(),
+            ..self.reader_settings.clone()
+        })
+    }
+
+    fn menu_panel(&mut self, ui: &mut egui::Ui) {
+        ui.heading("Participant");
+        egui::ComboBox::from_id_source("selected_participant")
+            .selected_text(participant_name(
+                &self.participants,
+                self.selected_participant,
+            ))
+            .show_ui(ui, |ui| {
+                for participant in self.participants.iter() {
+                    ui.selectable_value(
+                        &mut self.selected_participant,
+                        participant.id,
+                        participant.name(),
+                    );
+                }
+            });
+        ui.add(
+            egui::DragValue::new(&mut self.new_participant_domain_id)
+                .clamp_range(0..=232)
+                .prefix("domain: "),
+        );
+        ui.add(egui::TextEdit::singleline(&mut self.new_participant_name).hint_text("name"));
+        if ui.button("Add participant").clicked() {
+            let entity_name = std::mem::take(&mut self.new_participant_name);
+            self.add_participant(self.new_participant_domain_id, entity_name);
         };
+        if self.participants.len() > 1 && ui.button("Remove participant").clicked() {
+            self.remove_participant(self.selected_participant);
+        };
+
+        ui.separator();
+        ui.heading("Publish");
+        for (kind, _) in SHAPE_KINDS {
+            if ui.button(*kind).clicked() {
+                self.publish_widget = Some(PublishWidget::new(
+                    kind.to_string(),
+                    self.qos_profiles.datawriter_profile_names(),
+                ));
+            };
+        }
 
         ui.separator();
         ui.label("Publish rate [ms]:");
         let mut rate = *self.planner.rate.lock().unwrap();
-        ui.add(egui::Slider::new(&mut rate, 5..=500));
+        ui.add(egui::Slider::new(&mut rate, PUBLISH_RATE_RANGE));
         *self.planner.rate.lock().unwrap() = rate;
 
+        ui.label("World size:");
+        let mut world_size = *self.planner.world_size.lock().unwrap();
+        ui.add(egui::DragValue::new(&mut world_size.x).clamp_range(50.0..=2000.0));
+        ui.add(egui::DragValue::new(&mut world_size.y).clamp_range(50.0..=2000.0));
+        *self.planner.world_size.lock().unwrap() = world_size;
+
         ui.separator();
         ui.heading("Subscribe");
-        if ui.button("Square").clicked() {
-            self.create_reader("Square", self.is_reliable_reader)
+        for (kind, _) in SHAPE_KINDS {
+            if ui.button(*kind).clicked() {
+                self.create_reader_with_topic(kind)
+            };
+        }
+        ui.add(egui::TextEdit::singleline(&mut self.reader_settings.name).hint_text("name"));
+        ui.add_enabled(
+            self.reader_settings.qos_profile.is_none(),
+            egui::Checkbox::new(&mut self.reader_settings.is_reliable, "reliable"),
+        );
+        qos_profile_combo_box(
+            ui,
+            "subscribe_qos_profile",
+            &mut self.reader_settings.qos_profile,
+            &self.qos_profiles.datareader_profile_names(),
+        );
+        ui.add(
+            egui::DragValue::new(&mut self.reader_settings.latency_budget_ms)
+                .clamp_range(0..=10_000)
+                .prefix("latency budget: ")
+                .suffix(" ms"),
+        );
+        ui.collapsing("Data lifecycle", |ui| {
+            delay_ui(
+                ui,
+                "purge no writers",
+                &mut self.reader_settings.autopurge_nowriter_samples_delay_ms,
+            );
+            delay_ui(
+                ui,
+                "purge disposed",
+                &mut self.reader_settings.autopurge_disposed_samples_delay_ms,
+            );
+        });
+        ui.collapsing("Resource limits", |ui| {
+            ui.add(&mut self.reader_settings.resource_limits)
+        });
+
+        ui.separator();
+        if ui.button("Discovery").clicked() {
+            if let Ok(shape_participant) = self.participant(self.selected_participant) {
+                self.discovery_widget = Some(DiscoveryWidget::new(
+                    &shape_participant.participant,
+                    self.runtime.handle().clone(),
+                ));
+            }
         };
-        if ui.button("Circle").clicked() {
-            self.create_reader("Circle", self.is_reliable_reader)
+        if ui.button("Scenario").clicked() {
+            self.scenario_widget = Some(ScenarioWidget::new());
         };
-        if ui.button("Triangle").clicked() {
-            self.create_reader("Triangle", self.is_reliable_reader)
+        if ui.button("Stress test").clicked() {
+            self.stress_test_widget = Some(StressTestWidget::default());
+        };
+        if ui.button("Entity QoS").clicked() {
+            match self.entity_qos_widget() {
+                Ok(entity_qos_widget) => self.entity_qos_widget = Some(entity_qos_widget),
+                Err(e) => self
+                    .event_log
+                    .error(format!("Couldn't get the entity QoS: {:?}", e)),
+            }
         };
-        ui.checkbox(&mut self.is_reliable_reader, "reliable");
+        if ui.button("Event log").clicked() {
+            self.is_event_log_open = true;
+        };
+    }
+}
+
+impl Drop for ShapesDemoApp {
+    /// Stops the background tasks and deletes the participants, so that the
+    /// other participants are notified that their endpoints are gone.
+    fn drop(&mut self) {
+        self.planner.stop();
+        if let Some(stress_test) = self.stress_test.take() {
+            stress_test.stop(&self.runtime);
+        }
+        // Dropping the readers aborts their tasks
+        self.reader_list.clear();
+        self.runtime.block_on(async {
+            for shape_participant in self.participants.iter() {
+                shape_participant
+                    .delete(&self.participant_factory)
+                    .await
+                    .ok();
+            }
+        });
     }
 }
 
 impl eframe::App for ShapesDemoApp {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
+        self.repaint_context.get_or_init(|| ctx.clone());
+        let due_actions = self
+            .timeline_runner
+            .as_mut()
+            .map(|timeline_runner| timeline_runner.due_actions())
+            .unwrap_or_default();
+        for action in due_actions {
+            self.apply_action(action);
+        }
+
         if let Some(publish_widget) = &mut self.publish_widget {
             let mut open = true;
             egui::Window::new("Publish")
@@ -307,15 +1823,169 @@ impl eframe::App for ShapesDemoApp {
         }
         if let Some(publish_widget) = &self.publish_widget {
             if let Some(color) = &publish_widget.selected_color {
-                self.create_writer(
-                    publish_widget.selected_shape.clone(),
-                    &color.clone(),
-                    publish_widget.is_reliable,
-                );
+                self.create_writer(WriterSettings {
+                    color: color.clone(),
+                    ..publish_widget.settings.clone()
+                });
                 self.publish_widget = None;
             }
         }
 
+        if let Some(discovery_widget) = &mut self.discovery_widget {
+            let mut open = true;
+            egui::Window::new("Discovery")
+                .open(&mut open)
+                .collapsible(false)
+                .show(ctx, |ui| {
+                    ui.add(discovery_widget);
+                });
+            if !open {
+                self.discovery_widget = None;
+            }
+        }
+
+        if let Some(mut scenario_widget) = self.scenario_widget.take() {
+            let mut open = true;
+            egui::Window::new("Scenario")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.add(&mut scenario_widget);
+                    if let Some(timeline_runner) = &self.timeline_runner {
+                        ui.label(format!(
+                            "Timeline: {}/{} actions",
+                            timeline_runner.executed_actions(),
+                            timeline_runner.total_actions()
+                        ));
+                    }
+                });
+            if scenario_widget.save_clicked {
+                scenario_widget.message = Some(match self.scenario().save(&scenario_widget.path) {
+                    Ok(()) => "Scenario saved".to_string(),
+                    Err(e) => e,
+                });
+            }
+            if scenario_widget.load_clicked {
+                scenario_widget.message = Some(match Scenario::from_file(&scenario_widget.path) {
+                    Ok(scenario) => {
+                        self.load_scenario(scenario);
+                        "Scenario loaded".to_string()
+                    }
+                    Err(e) => e,
+                });
+            }
+            if scenario_widget.run_timeline_clicked {
+                scenario_widget.message = Some(match Timeline::from_file(&scenario_widget.path) {
+                    Ok(timeline) => {
+                        self.run_timeline(timeline);
+                        "Timeline started".to_string()
+                    }
+                    Err(e) => e,
+                });
+            }
+            if open {
+                self.scenario_widget = Some(scenario_widget);
+            }
+        }
+
+        let written_samples = self.planner.written_samples.load(Ordering::Relaxed);
+        self.publish_meter
+            .add(written_samples.wrapping_sub(self.last_written_samples));
+        self.last_written_samples = written_samples;
+        let drained_samples = self.drained_samples.load(Ordering::Relaxed);
+        self.drain_meter
+            .add(drained_samples.wrapping_sub(self.last_drained_samples));
+        self.last_drained_samples = drained_samples;
+        if let Some(mut stress_test_widget) = self.stress_test_widget.take() {
+            stress_test_widget.publish_throughput = self.publish_meter.rate();
+            stress_test_widget.drain_rate = self.drain_meter.rate();
+            stress_test_widget.frame_time_ms = ctx.input(|i| i.unstable_dt) * 1000.0;
+            let mut open = true;
+            egui::Window::new("Stress test")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.add(&mut stress_test_widget);
+                });
+            if stress_test_widget.start_clicked {
+                self.start_stress_test(
+                    &stress_test_widget.topic_name.clone(),
+                    stress_test_widget.writer_count,
+                    stress_test_widget.instances_per_writer,
+                );
+            }
+            if stress_test_widget.stop_clicked {
+                self.stop_stress_test();
+            }
+            if open {
+                self.stress_test_widget = Some(stress_test_widget);
+            }
+        }
+
+        if let Some(mut entity_qos_widget) = self.entity_qos_widget.take() {
+            let mut open = true;
+            egui::Window::new("Entity QoS")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    ui.add(&mut entity_qos_widget);
+                });
+            if entity_qos_widget.apply_clicked {
+                match self.apply_entity_qos(&entity_qos_widget) {
+                    Ok(()) => self.event_log.info("Applied entity QoS"),
+                    Err(e) => self
+                        .event_log
+                        .error(format!("Couldn't apply entity QoS: {:?}", e)),
+                }
+            }
+            if open {
+                self.entity_qos_widget = Some(entity_qos_widget);
+            }
+        }
+
+        egui::Window::new("Event log")
+            .open(&mut self.is_event_log_open)
+            .collapsible(false)
+            .show(ctx, |ui| {
+                ui.add(&self.event_log);
+            });
+        for shape_participant in self.participants.iter() {
+            for topic_name in self
+                .runtime
+                .block_on(shape_participant.topic_registry.inconsistent_topics())
+            {
+                self.event_log.error(format!(
+                    "{} discovered topic {} with an inconsistent type",
+                    shape_participant.name(),
+                    topic_name
+                ));
+            }
+        }
+        self.event_log.show_toasts(ctx);
+
+        if let Some(entity) = self.selected_entity {
+            let matched_endpoints = self.matched_endpoints(entity);
+            let mut open = true;
+            egui::Window::new("Matched endpoints")
+                .open(&mut open)
+                .collapsible(false)
+                .resizable(false)
+                .show(ctx, |ui| {
+                    if matched_endpoints.is_empty() {
+                        ui.label("No matched endpoints");
+                    }
+                    for guid in matched_endpoints.iter() {
+                        ui.monospace(guid);
+                    }
+                });
+            if !open {
+                self.selected_entity = None;
+            }
+        }
+
         let is_landscape = ctx.screen_rect().aspect_ratio() > 1.0;
 
         if is_landscape {
@@ -323,78 +1993,322 @@ impl eframe::App for ShapesDemoApp {
                 .max_width(100.0)
                 .resizable(false)
                 .show(ctx, |ui| self.menu_panel(ui));
+            let mut lifecycle_change = None;
             egui::TopBottomPanel::bottom("writer_list")
                 .min_height(100.0)
                 .show(ctx, |ui| {
-                    egui::Grid::new("my_grid")
-                        .num_columns(4)
-                        .spacing([40.0, 4.0])
-                        .striped(true)
-                        .show(ui, |ui| {
-                            ui.label("");
-                            ui.label("Topic");
-                            ui.label("Color");
-                            ui.label("Reliability");
-                            ui.end_row();
-                            for shape_writer in self.writer_list.lock().unwrap().iter() {
-                                ui.label("writer");
-                                ui.label(shape_writer.writer.get_topic().get_name());
-                                ui.label(shape_writer.color());
-                                ui.label(reliability_kind(
-                                    &shape_writer.writer.get_qos().unwrap().reliability.kind,
+                    // The writers are cloned out of the list, so that their
+                    // status is queried without holding the lock of the planner
+                    let mut writer_rows = Vec::new();
+                    let mut stress_test_writers = None;
+                    for (index, shape_writer) in self.writer_list.lock().unwrap().iter().enumerate()
+                    {
+                        if shape_writer.is_stress_test {
+                            let (_, _, writer_count, instance_count) = stress_test_writers
+                                .get_or_insert((
+                                    shape_writer.participant_id,
+                                    shape_writer.writer.clone(),
+                                    0,
+                                    0,
                                 ));
+                            *writer_count += 1;
+                            *instance_count += shape_writer.instances.len();
+                        } else {
+                            writer_rows.push((
+                                index,
+                                shape_writer.participant_id,
+                                shape_writer.settings.name.clone(),
+                                shape_writer.writer.clone(),
+                                shape_writer.color(),
+                                shape_writer.lifecycle,
+                            ));
+                        }
+                    }
+                    egui::ScrollArea::vertical().show(ui, |ui| {
+                        egui::Grid::new("my_grid")
+                            .num_columns(14)
+                            .spacing([40.0, 4.0])
+                            .striped(true)
+                            .show(ui, |ui| {
+                                ui.label("");
+                                ui.label("Participant");
+                                ui.label("Name");
+                                ui.label("Topic");
+                                ui.label("Color");
+                                ui.label("Reliability");
+                                ui.label("Budget [ms]");
+                                ui.label("Priority");
+                                ui.label("Matched");
+                                ui.label("Rate [1/s]");
+                                ui.label("Latency [ms]");
+                                ui.label("Lost");
+                                ui.label("Rejected");
+                                ui.label("");
                                 ui.end_row();
-                            }
-                            ui.end_row();
-                            for reader in self.reader_list.iter() {
-                                ui.label("reader");
-                                ui.label(reader.get_topicdescription().get_name());
-                                ui.label("*");
-                                ui.label(reliability_kind(
-                                    &reader.get_qos().unwrap().reliability.kind,
-                                ));
+                                // The stress test writers are summarized in one row
+                                if let Some((
+                                    participant_id,
+                                    writer,
+                                    writer_count,
+                                    instance_count,
+                                )) = stress_test_writers
+                                {
+                                    ui.label("stress test");
+                                    ui.label(participant_name(&self.participants, participant_id));
+                                    ui.label("");
+                                    ui.label(writer.get_topic().get_name());
+                                    ui.label(format!(
+                                        "{} writers, {} instances",
+                                        writer_count, instance_count
+                                    ));
+                                    ui.label(
+                                        self.runtime
+                                            .block_on(writer.get_qos())
+                                            .map(|qos| reliability_kind(&qos.reliability.kind))
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.end_row();
+                                }
+                                for (index, participant_id, name, writer, color, lifecycle) in
+                                    writer_rows
+                                {
+                                    let entity = SelectedEntity::Writer(index);
+                                    if ui
+                                        .selectable_label(
+                                            self.selected_entity == Some(entity),
+                                            "writer",
+                                        )
+                                        .clicked()
+                                    {
+                                        self.selected_entity = Some(entity);
+                                    }
+                                    ui.label(participant_name(&self.participants, participant_id));
+                                    ui.label(name);
+                                    ui.label(writer.get_topic().get_name());
+                                    ui.label(color);
+                                    let qos = self.runtime.block_on(writer.get_qos()).ok();
+                                    ui.label(
+                                        qos.as_ref()
+                                            .map(|qos| reliability_kind(&qos.reliability.kind))
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.label(
+                                        qos.as_ref()
+                                            .map(|qos| {
+                                                format_duration_ms(&qos.latency_budget.duration)
+                                            })
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.label(
+                                        qos.as_ref()
+                                            .map(|qos| qos.transport_priority.value.to_string())
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.label(
+                                        self.runtime
+                                            .block_on(writer.get_publication_matched_status())
+                                            .map(|status| status.current_count.to_string())
+                                            .unwrap_or_default(),
+                                    );
+                                    // Writers have no reception statistics
+                                    for _ in 0..4 {
+                                        ui.label("");
+                                    }
+                                    ui.horizontal(|ui| {
+                                        if lifecycle == WriterLifecycle::Alive {
+                                            if ui.button("Unregister").clicked() {
+                                                lifecycle_change =
+                                                    Some((index, WriterLifecycle::Unregistered));
+                                            }
+                                            if ui.button("Dispose").clicked() {
+                                                lifecycle_change =
+                                                    Some((index, WriterLifecycle::Disposed));
+                                            }
+                                        } else if ui.button("Resume").clicked() {
+                                            lifecycle_change =
+                                                Some((index, WriterLifecycle::Alive));
+                                        }
+                                    });
+                                    ui.end_row();
+                                }
                                 ui.end_row();
-                            }
-                        })
+                                for (
+                                    index,
+                                    ShapeReader {
+                                        participant_id,
+                                        reader,
+                                        task,
+                                        settings,
+                                    },
+                                ) in self.reader_list.iter().enumerate()
+                                {
+                                    let statistics = task.scene().statistics.clone();
+                                    let entity = SelectedEntity::Reader(index);
+                                    if ui
+                                        .selectable_label(
+                                            self.selected_entity == Some(entity),
+                                            "reader",
+                                        )
+                                        .clicked()
+                                    {
+                                        self.selected_entity = Some(entity);
+                                    }
+                                    ui.label(participant_name(&self.participants, *participant_id));
+                                    ui.label(&settings.name);
+                                    ui.label(reader.get_topicdescription().get_name());
+                                    ui.label("*");
+                                    let qos = self.runtime.block_on(reader.get_qos()).ok();
+                                    ui.label(
+                                        qos.as_ref()
+                                            .map(|qos| reliability_kind(&qos.reliability.kind))
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.label(
+                                        qos.as_ref()
+                                            .map(|qos| {
+                                                format_duration_ms(&qos.latency_budget.duration)
+                                            })
+                                            .unwrap_or_default(),
+                                    );
+                                    // Readers have no transport priority
+                                    ui.label("");
+                                    ui.label(
+                                        self.runtime
+                                            .block_on(reader.get_subscription_matched_status())
+                                            .map(|status| status.current_count.to_string())
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.label(format!("{:.1}", statistics.samples_per_second()));
+                                    ui.label(
+                                        statistics
+                                            .latency_ms()
+                                            .map(|latency| format!("{:.1}", latency))
+                                            .unwrap_or_else(|| "-".to_string()),
+                                    );
+                                    ui.label(
+                                        self.runtime
+                                            .block_on(reader.get_sample_lost_status())
+                                            .map(|status| status.total_count.to_string())
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.label(
+                                        self.runtime
+                                            .block_on(reader.get_sample_rejected_status())
+                                            .map(|status| format_rejected(&status))
+                                            .unwrap_or_default(),
+                                    );
+                                    ui.add(Sparkline::new(statistics.rate_history()));
+                                    ui.end_row();
+                                }
+                            })
+                    });
                 });
+            if let Some((index, lifecycle)) = lifecycle_change {
+                self.set_writer_lifecycle(index, lifecycle);
+            }
         } else {
             egui::TopBottomPanel::top("menu_panel").show(ctx, |ui| self.menu_panel(ui));
         }
 
         egui::CentralPanel::default().show(ctx, |ui| {
-            let rect_size = egui::vec2(235.0, 265.0);
-
-            let mut shape_list = Vec::new();
-            for reader in &self.reader_list {
-                let kind = reader.get_topicdescription().get_name();
-                let mut previous_handle = None;
-                while let Ok(samples) = reader.read_next_instance(
-                    1,
-                    previous_handle,
-                    ANY_SAMPLE_STATE,
-                    ANY_VIEW_STATE,
-                    ANY_INSTANCE_STATE,
-                ) {
-                    if let Some(sample) = samples.first() {
-                        previous_handle = Some(sample.sample_info().instance_handle);
-                        if let Ok(shape_type) = sample.data() {
-                            let shape = GuiShape::from_shape_type(kind.clone(), &shape_type);
-                            shape_list.push(shape);
+            let rect_size = *self.planner.world_size.lock().unwrap();
+
+            // Each participant has its own canvas, side by side
+            let mut canvases: Vec<_> = self
+                .participants
+                .iter()
+                .map(|participant| {
+                    // With coherent access the shapes of a coherent set are
+                    // shown together, so they move in lock-step
+                    let presentation = self
+                        .runtime
+                        .block_on(participant.subscriber.get_qos())
+                        .ok()
+                        .and_then(|qos| presentation_label(&qos.presentation));
+                    (
+                        participant.id,
+                        participant.name(),
+                        presentation,
+                        Vec::new(),
+                        Vec::new(),
+                    )
+                })
+                .collect();
+            for shape_reader in self.reader_list.iter() {
+                if let Some((_, _, _, shape_list, warnings)) = canvases
+                    .iter_mut()
+                    .find(|(id, ..)| *id == shape_reader.participant_id)
+                {
+                    shape_list.extend(shape_reader.task.scene().shapes.iter().cloned());
+                    // Readers limited by their resource limits only show the
+                    // instances they could store
+                    if let Ok(status) = self
+                        .runtime
+                        .block_on(shape_reader.reader.get_sample_rejected_status())
+                    {
+                        if status.total_count > 0 {
+                            warnings.push(format!(
+                                "{} reader rejected {}",
+                                shape_reader.settings.topic_name,
+                                format_rejected(&status)
+                            ));
                         }
                     }
                 }
             }
 
-            let time = ui.input(|i| i.time);
-            let time_delta = (time - self.time) as f32;
-            self.time = time;
-            for writer in self.writer_list.lock().unwrap().iter_mut() {
-                writer.shape.move_within_rect(rect_size, time_delta);
-                shape_list.push(writer.shape.gui_shape().clone());
+            let mut has_writers = false;
+            for writer in self.writer_list.lock().unwrap().iter() {
+                has_writers = true;
+                if let Some((_, _, _, shape_list, _)) = canvases
+                    .iter_mut()
+                    .find(|(id, ..)| *id == writer.participant_id)
+                {
+                    let instance_state = writer.instance_state();
+                    for instance in writer.instances.iter() {
+                        let mut shape = instance.shape.gui_shape().clone();
+                        shape.set_instance_state(instance_state);
+                        shape_list.push(shape);
+                    }
+                }
             }
-            ui.add(ShapesWidget::new(rect_size, shape_list.as_slice()));
+            let canvas_count = canvases.len();
+            let is_batched = self.stress_test.is_some();
+            ui.columns(canvas_count, |columns| {
+                for (ui, (_, name, presentation, shape_list, warnings)) in
+                    columns.iter_mut().zip(canvases)
+                {
+                    if canvas_count > 1 {
+                        ui.label(name);
+                    }
+                    if let Some(presentation) = presentation {
+                        ui.label(presentation);
+                    }
+                    for warning in warnings {
+                        ui.colored_label(ui.visuals().warn_fg_color, warning);
+                    }
+                    ui.add(ShapesWidget::new(rect_size, shape_list.as_slice()).batched(is_batched));
+                }
+            });
 
-            ctx.request_repaint_after(std::time::Duration::from_millis(40));
+            // Readers request a repaint when they receive data, only the local
+            // writers need to be animated continuously
+            if has_writers {
+                ctx.request_repaint_after(std::time::Duration::from_millis(40));
+            }
         });
+
+        // The timeline actions and the discovered entities are only checked
+        // when the GUI is updated
+        if let Some(delay) = self
+            .timeline_runner
+            .as_ref()
+            .and_then(TimelineRunner::time_to_next_action)
+        {
+            ctx.request_repaint_after(delay);
+        }
+        if self.discovery_widget.is_some() {
+            ctx.request_repaint_after(std::time::Duration::from_millis(500));
+        }
     }
 }
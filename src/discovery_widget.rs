@@ -0,0 +1,236 @@
+use dust_dds::{
+    builtin_topics::{
+        BuiltInTopicKey, ParticipantBuiltinTopicData, PublicationBuiltinTopicData,
+        SubscriptionBuiltinTopicData, TopicBuiltinTopicData,
+    },
+    dds_async::{data_reader::DataReaderAsync, domain_participant::DomainParticipantAsync},
+    infrastructure::{qos_policy::DurabilityQosPolicyKind, time::DurationKind},
+    subscription::sample_info::{InstanceStateKind, ANY_SAMPLE_STATE, ANY_VIEW_STATE},
+    topic_definition::type_support::DdsDeserialize,
+};
+use eframe::egui;
+
+use super::app::reliability_kind;
+
+const MAX_SAMPLES: i32 = i32::MAX;
+
+/// Shows the user, group and topic data as text since that is how the demo
+/// sets them.
+fn format_data(value: &[u8]) -> String {
+    String::from_utf8_lossy(value).into_owned()
+}
+
+pub(crate) fn format_key(key: &BuiltInTopicKey) -> String {
+    key.value.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+pub(crate) fn durability_kind(kind: &DurabilityQosPolicyKind) -> &'static str {
+    match kind {
+        DurabilityQosPolicyKind::Volatile => "Volatile",
+        DurabilityQosPolicyKind::TransientLocal => "Transient local",
+    }
+}
+
+pub(crate) fn format_duration_ms(duration: &DurationKind) -> String {
+    match duration {
+        DurationKind::Finite(duration) => {
+            let ms = duration.sec() as f64 * 1000.0 + duration.nanosec() as f64 / 1_000_000.0;
+            format!("{}", ms)
+        }
+        DurationKind::Infinite => "Infinite".to_string(),
+    }
+}
+
+fn read_alive<Foo>(
+    runtime: &tokio::runtime::Handle,
+    reader: &Option<DataReaderAsync<Foo>>,
+) -> Vec<Foo>
+where
+    Foo: for<'de> DdsDeserialize<'de>,
+{
+    let Some(reader) = reader else {
+        return Vec::new();
+    };
+    runtime
+        .block_on(reader.read(
+            MAX_SAMPLES,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            &[InstanceStateKind::Alive],
+        ))
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|sample| sample.data().ok())
+        .collect()
+}
+
+pub struct DiscoveryWidget {
+    runtime: tokio::runtime::Handle,
+    participant_reader: Option<DataReaderAsync<ParticipantBuiltinTopicData>>,
+    publication_reader: Option<DataReaderAsync<PublicationBuiltinTopicData>>,
+    subscription_reader: Option<DataReaderAsync<SubscriptionBuiltinTopicData>>,
+    topic_reader: Option<DataReaderAsync<TopicBuiltinTopicData>>,
+}
+
+impl DiscoveryWidget {
+    pub fn new(participant: &DomainParticipantAsync, runtime: tokio::runtime::Handle) -> Self {
+        let builtin_subscriber = participant.get_builtin_subscriber();
+        runtime.clone().block_on(async {
+            Self {
+                runtime,
+                participant_reader: builtin_subscriber
+                    .lookup_datareader("DCPSParticipant")
+                    .await
+                    .unwrap_or_default(),
+                publication_reader: builtin_subscriber
+                    .lookup_datareader("DCPSPublication")
+                    .await
+                    .unwrap_or_default(),
+                subscription_reader: builtin_subscriber
+                    .lookup_datareader("DCPSSubscription")
+                    .await
+                    .unwrap_or_default(),
+                topic_reader: builtin_subscriber
+                    .lookup_datareader("DCPSTopic")
+                    .await
+                    .unwrap_or_default(),
+            }
+        })
+    }
+
+    fn participants_ui(&self, ui: &mut egui::Ui) {
+        let participants = read_alive(&self.runtime, &self.participant_reader);
+        egui::CollapsingHeader::new(format!("Participants ({})", participants.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("discovered_participants")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("GUID");
+                        ui.label("User data");
+                        ui.end_row();
+                        for participant in participants.iter() {
+                            ui.monospace(format_key(participant.key()));
+                            ui.label(format_data(&participant.user_data().value));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn publications_ui(&self, ui: &mut egui::Ui) {
+        let publications = read_alive(&self.runtime, &self.publication_reader);
+        egui::CollapsingHeader::new(format!("Writers ({})", publications.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("discovered_publications")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("GUID");
+                        ui.label("Participant");
+                        ui.label("Topic");
+                        ui.label("Type");
+                        ui.label("Reliability");
+                        ui.label("Durability");
+                        ui.label("Latency budget [ms]");
+                        ui.label("User data");
+                        ui.label("Topic data");
+                        ui.label("Group data");
+                        ui.end_row();
+                        for publication in publications.iter() {
+                            ui.monospace(format_key(publication.key()));
+                            ui.monospace(format_key(publication.participant_key()));
+                            ui.label(publication.topic_name());
+                            ui.label(publication.get_type_name());
+                            ui.label(reliability_kind(&publication.reliability().kind));
+                            ui.label(durability_kind(&publication.durability().kind));
+                            ui.label(format_duration_ms(&publication.latency_budget().duration));
+                            ui.label(format_data(&publication.user_data().value));
+                            ui.label(format_data(&publication.topic_data().value));
+                            ui.label(format_data(&publication.group_data().value));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn subscriptions_ui(&self, ui: &mut egui::Ui) {
+        let subscriptions = read_alive(&self.runtime, &self.subscription_reader);
+        egui::CollapsingHeader::new(format!("Readers ({})", subscriptions.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("discovered_subscriptions")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("GUID");
+                        ui.label("Participant");
+                        ui.label("Topic");
+                        ui.label("Type");
+                        ui.label("Reliability");
+                        ui.label("Durability");
+                        ui.label("Latency budget [ms]");
+                        ui.label("User data");
+                        ui.label("Topic data");
+                        ui.label("Group data");
+                        ui.end_row();
+                        for subscription in subscriptions.iter() {
+                            ui.monospace(format_key(subscription.key()));
+                            ui.monospace(format_key(subscription.participant_key()));
+                            ui.label(subscription.topic_name());
+                            ui.label(subscription.get_type_name());
+                            ui.label(reliability_kind(&subscription.reliability().kind));
+                            ui.label(durability_kind(&subscription.durability().kind));
+                            ui.label(format_duration_ms(&subscription.latency_budget().duration));
+                            ui.label(format_data(&subscription.user_data().value));
+                            ui.label(format_data(&subscription.topic_data().value));
+                            ui.label(format_data(&subscription.group_data().value));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+
+    fn topics_ui(&self, ui: &mut egui::Ui) {
+        let topics = read_alive(&self.runtime, &self.topic_reader);
+        egui::CollapsingHeader::new(format!("Topics ({})", topics.len()))
+            .default_open(true)
+            .show(ui, |ui| {
+                egui::Grid::new("discovered_topics")
+                    .striped(true)
+                    .show(ui, |ui| {
+                        ui.label("GUID");
+                        ui.label("Name");
+                        ui.label("Type");
+                        ui.label("Reliability");
+                        ui.label("Durability");
+                        ui.label("Latency budget [ms]");
+                        ui.label("Topic data");
+                        ui.end_row();
+                        for topic in topics.iter() {
+                            ui.monospace(format_key(topic.key()));
+                            ui.label(topic.name());
+                            ui.label(topic.get_type_name());
+                            ui.label(reliability_kind(&topic.reliability().kind));
+                            ui.label(durability_kind(&topic.durability().kind));
+                            ui.label(format_duration_ms(&topic.latency_budget().duration));
+                            ui.label(format_data(&topic.topic_data().value));
+                            ui.end_row();
+                        }
+                    });
+            });
+    }
+}
+
+impl egui::Widget for &mut DiscoveryWidget {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            egui::ScrollArea::vertical().show(ui, |ui| {
+                self.participants_ui(ui);
+                self.publications_ui(ui);
+                self.subscriptions_ui(ui);
+                self.topics_ui(ui);
+            });
+        })
+        .response
+    }
+}
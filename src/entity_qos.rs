@@ -0,0 +1,132 @@
+use dust_dds::infrastructure::{
+    qos::TopicQos,
+    qos_policy::{
+        DurabilityQosPolicyKind, PresentationQosPolicy, PresentationQosPolicyAccessScopeKind,
+        ReliabilityQosPolicyKind,
+    },
+};
+use eframe::egui;
+
+use super::{app::access_scope_kind, discovery_widget::durability_kind};
+
+const DURABILITY_KINDS: [DurabilityQosPolicyKind; 2] = [
+    DurabilityQosPolicyKind::Volatile,
+    DurabilityQosPolicyKind::TransientLocal,
+];
+
+const ACCESS_SCOPE_KINDS: [PresentationQosPolicyAccessScopeKind; 2] = [
+    PresentationQosPolicyAccessScopeKind::Instance,
+    PresentationQosPolicyAccessScopeKind::Topic,
+];
+
+/// Dialog to edit the QoS of the participant, publisher, subscriber and topics
+/// of the application. The data policies are edited as text.
+pub struct EntityQosWidget {
+    pub user_data: String,
+    pub publisher_group_data: String,
+    pub subscriber_group_data: String,
+    /// Presentation of both the publisher and the subscriber.
+    pub presentation: PresentationQosPolicy,
+    pub topic_data: String,
+    pub topic_qos: TopicQos,
+    pub apply_clicked: bool,
+}
+
+impl EntityQosWidget {
+    pub fn new(
+        user_data: &[u8],
+        publisher_group_data: &[u8],
+        subscriber_group_data: &[u8],
+        presentation: PresentationQosPolicy,
+        topic_qos: TopicQos,
+    ) -> Self {
+        Self {
+            user_data: String::from_utf8_lossy(user_data).into_owned(),
+            publisher_group_data: String::from_utf8_lossy(publisher_group_data).into_owned(),
+            subscriber_group_data: String::from_utf8_lossy(subscriber_group_data).into_owned(),
+            presentation,
+            topic_data: String::from_utf8_lossy(&topic_qos.topic_data.value).into_owned(),
+            topic_qos,
+            apply_clicked: false,
+        }
+    }
+}
+
+impl egui::Widget for &mut EntityQosWidget {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            egui::Grid::new("entity_qos").show(ui, |ui| {
+                ui.label("Participant user data:");
+                ui.text_edit_singleline(&mut self.user_data);
+                ui.end_row();
+                ui.label("Publisher group data:");
+                ui.text_edit_singleline(&mut self.publisher_group_data);
+                ui.end_row();
+                ui.label("Subscriber group data:");
+                ui.text_edit_singleline(&mut self.subscriber_group_data);
+                ui.end_row();
+            });
+            ui.collapsing("Presentation", |ui| {
+                egui::Grid::new("presentation").show(ui, |ui| {
+                    ui.label("Access scope:");
+                    egui::ComboBox::from_id_source("presentation_access_scope")
+                        .selected_text(access_scope_kind(&self.presentation.access_scope))
+                        .show_ui(ui, |ui| {
+                            for kind in ACCESS_SCOPE_KINDS {
+                                let text = access_scope_kind(&kind);
+                                ui.selectable_value(
+                                    &mut self.presentation.access_scope,
+                                    kind,
+                                    text,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                    ui.label("Access:");
+                    ui.horizontal(|ui| {
+                        ui.checkbox(&mut self.presentation.coherent_access, "coherent");
+                        ui.checkbox(&mut self.presentation.ordered_access, "ordered");
+                    });
+                    ui.end_row();
+                });
+                ui.label(
+                    "Applies to the publisher and subscriber, only without writers and readers.",
+                );
+            });
+            ui.collapsing("Topic QoS", |ui| {
+                egui::Grid::new("topic_qos").show(ui, |ui| {
+                    ui.label("Topic data:");
+                    ui.text_edit_singleline(&mut self.topic_data);
+                    ui.end_row();
+                    ui.label("Reliability:");
+                    let mut is_reliable =
+                        self.topic_qos.reliability.kind == ReliabilityQosPolicyKind::Reliable;
+                    ui.checkbox(&mut is_reliable, "reliable");
+                    self.topic_qos.reliability.kind = if is_reliable {
+                        ReliabilityQosPolicyKind::Reliable
+                    } else {
+                        ReliabilityQosPolicyKind::BestEffort
+                    };
+                    ui.end_row();
+                    ui.label("Durability:");
+                    egui::ComboBox::from_id_source("topic_durability")
+                        .selected_text(durability_kind(&self.topic_qos.durability.kind))
+                        .show_ui(ui, |ui| {
+                            for kind in DURABILITY_KINDS {
+                                let text = durability_kind(&kind);
+                                ui.selectable_value(
+                                    &mut self.topic_qos.durability.kind,
+                                    kind,
+                                    text,
+                                );
+                            }
+                        });
+                    ui.end_row();
+                });
+                ui.label("Applies to new topics, existing topics only accept the topic data.");
+            });
+            self.apply_clicked = ui.button("Apply").clicked();
+        })
+        .response
+    }
+}
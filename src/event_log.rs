@@ -0,0 +1,115 @@
+use std::{
+    collections::VecDeque,
+    sync::{Arc, Mutex},
+    time::{Duration, Instant},
+};
+
+use eframe::egui;
+
+const MAX_EVENTS: usize = 500;
+const MAX_TOASTS: usize = 5;
+const TOAST_DURATION: Duration = Duration::from_secs(5);
+
+#[derive(Clone, Copy, PartialEq)]
+pub enum Severity {
+    Info,
+    Error,
+}
+
+pub struct Event {
+    pub time: Instant,
+    pub severity: Severity,
+    pub message: String,
+}
+
+/// Log of the events of the application. It is shared with the background
+/// tasks so that their errors are reported in the GUI instead of panicking.
+#[derive(Clone)]
+pub struct EventLog {
+    start: Instant,
+    events: Arc<Mutex<VecDeque<Event>>>,
+}
+
+impl Default for EventLog {
+    fn default() -> Self {
+        Self {
+            start: Instant::now(),
+            events: Arc::new(Mutex::new(VecDeque::new())),
+        }
+    }
+}
+
+impl EventLog {
+    pub fn info(&self, message: impl Into<String>) {
+        self.push(Severity::Info, message.into());
+    }
+
+    pub fn error(&self, message: impl Into<String>) {
+        self.push(Severity::Error, message.into());
+    }
+
+    fn push(&self, severity: Severity, message: String) {
+        let mut events = self.events.lock().unwrap();
+        if events.len() == MAX_EVENTS {
+            events.pop_front();
+        }
+        events.push_back(Event {
+            time: Instant::now(),
+            severity,
+            message,
+        });
+    }
+
+    /// Shows the most recent errors as toasts in the bottom right corner.
+    pub fn show_toasts(&self, ctx: &egui::Context) {
+        let events = self.events.lock().unwrap();
+        let toasts: Vec<_> = events
+            .iter()
+            .rev()
+            .filter(|event| {
+                event.severity == Severity::Error && event.time.elapsed() < TOAST_DURATION
+            })
+            .take(MAX_TOASTS)
+            .collect();
+        let Some(oldest) = toasts.last() else {
+            return;
+        };
+        egui::Area::new("toasts")
+            .anchor(egui::Align2::RIGHT_BOTTOM, egui::vec2(-10.0, -10.0))
+            .show(ctx, |ui| {
+                for event in toasts.iter().rev() {
+                    egui::Frame::popup(ui.style()).show(ui, |ui| {
+                        ui.colored_label(ui.visuals().error_fg_color, &event.message);
+                    });
+                }
+            });
+        ctx.request_repaint_after(TOAST_DURATION.saturating_sub(oldest.time.elapsed()));
+    }
+}
+
+impl egui::Widget for &EventLog {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            if ui.button("Clear").clicked() {
+                self.events.lock().unwrap().clear();
+            }
+            egui::ScrollArea::vertical()
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for event in self.events.lock().unwrap().iter() {
+                        let time = format!("{:.1} s", (event.time - self.start).as_secs_f32());
+                        ui.horizontal(|ui| {
+                            ui.label(time);
+                            match event.severity {
+                                Severity::Info => ui.label(&event.message),
+                                Severity::Error => {
+                                    ui.colored_label(ui.visuals().error_fg_color, &event.message)
+                                }
+                            };
+                        });
+                    }
+                });
+        })
+        .response
+    }
+}
@@ -0,0 +1,77 @@
+use std::time::{Duration, Instant};
+
+use eframe::egui;
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ImpairmentSettings {
+    pub drop_percentage: u32,
+    pub jitter_ms: u64,
+    pub batch_size: usize,
+}
+
+impl Default for ImpairmentSettings {
+    fn default() -> Self {
+        Self {
+            drop_percentage: 0,
+            jitter_ms: 0,
+            batch_size: 1,
+        }
+    }
+}
+
+impl egui::Widget for &mut ImpairmentSettings {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            ui.label("Drop [%]:");
+            ui.add(egui::Slider::new(&mut self.drop_percentage, 0..=100));
+            ui.label("Jitter [ms]:");
+            ui.add(egui::Slider::new(&mut self.jitter_ms, 0..=1000));
+            ui.label("Batch size:");
+            ui.add(egui::Slider::new(&mut self.batch_size, 1..=20));
+        })
+        .response
+    }
+}
+
+pub struct Impairment<T> {
+    settings: ImpairmentSettings,
+    pending: Vec<(Instant, T)>,
+}
+
+impl<T> Impairment<T> {
+    pub fn new(settings: ImpairmentSettings) -> Self {
+        Self {
+            settings,
+            pending: Vec::new(),
+        }
+    }
+
+    /// Drops the samples that are not written yet.
+    pub fn clear(&mut self) {
+        self.pending.clear();
+    }
+
+    /// Takes the next sample to be published and returns the samples that
+    /// are due to be written now, after applying drop, jitter and batching.
+    pub fn process(&mut self, sample: T) -> Vec<T> {
+        let mut rng = rand::thread_rng();
+        let now = Instant::now();
+        if !rng.gen_ratio(self.settings.drop_percentage.min(100), 100) {
+            let jitter = Duration::from_millis(rng.gen_range(0..=self.settings.jitter_ms));
+            self.pending.push((now + jitter, sample));
+        }
+
+        let due_count = self.pending.iter().filter(|(due, _)| *due <= now).count();
+        if due_count < self.settings.batch_size {
+            return Vec::new();
+        }
+        self.pending.sort_by_key(|(due, _)| *due);
+        self.pending
+            .drain(..due_count)
+            .map(|(_, sample)| sample)
+            .collect()
+    }
+}
@@ -1,2 +1,14 @@
 pub mod app;
-mod shapes_widget;
\ No newline at end of file
+mod discovery_widget;
+mod entity_qos;
+mod event_log;
+mod impairment;
+pub mod qos_profile;
+mod reader_statistics;
+mod reader_task;
+mod resource_limits;
+pub mod scenario;
+mod shapes_widget;
+mod stress_test;
+pub mod timeline;
+mod topic_registry;
\ No newline at end of file
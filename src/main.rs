@@ -1,9 +1,54 @@
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")] // hide console window on Windows in release
 
 pub mod app;
+mod discovery_widget;
+mod entity_qos;
+mod event_log;
+mod impairment;
+mod qos_profile;
+mod reader_statistics;
+mod reader_task;
+mod resource_limits;
+mod scenario;
 mod shapes_widget;
+mod stress_test;
+mod timeline;
+mod topic_registry;
 
 fn main() -> Result<(), eframe::Error> {
+    let mut qos_profiles = qos_profile::QosProfileLibrary::default();
+    let mut scenario = None;
+    let mut timeline = None;
+    let mut args = std::env::args().skip(1);
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--qos-file" => {
+                let path = args
+                    .next()
+                    .unwrap_or_else(|| exit_with_error("Missing QoS file path"));
+                qos_profiles = qos_profile::QosProfileLibrary::from_file(&path)
+                    .unwrap_or_else(|e| exit_with_error(&e));
+            }
+            "--scenario" => {
+                let path = args
+                    .next()
+                    .unwrap_or_else(|| exit_with_error("Missing scenario file path"));
+                scenario = Some(
+                    scenario::Scenario::from_file(&path).unwrap_or_else(|e| exit_with_error(&e)),
+                );
+            }
+            "--timeline" => {
+                let path = args
+                    .next()
+                    .unwrap_or_else(|| exit_with_error("Missing timeline file path"));
+                timeline = Some(
+                    timeline::Timeline::from_file(&path).unwrap_or_else(|e| exit_with_error(&e)),
+                );
+            }
+            _ => exit_with_error(&format!("Unknown argument {}", arg)),
+        }
+    }
+
     const ICON: &[u8] = include_bytes!("../res/logo.png");
     let icon = eframe::icon_data::from_png_bytes(ICON).expect("Failed to open icon");
     let viewport = eframe::egui::viewport::ViewportBuilder {
@@ -19,6 +64,21 @@ fn main() -> Result<(), eframe::Error> {
     eframe::run_native(
         "Dust DDS Shapes Demo",
         options,
-        Box::new(|_cc| Box::<app::ShapesDemoApp>::default()),
+        Box::new(|_cc| {
+            let mut app =
+                app::ShapesDemoApp::new(qos_profiles).unwrap_or_else(|e| exit_with_error(&e));
+            if let Some(scenario) = scenario {
+                app.load_scenario(scenario);
+            }
+            if let Some(timeline) = timeline {
+                app.run_timeline(timeline);
+            }
+            Box::new(app)
+        }),
     )
 }
+
+fn exit_with_error(message: &str) -> ! {
+    eprintln!("{}", message);
+    std::process::exit(1)
+}
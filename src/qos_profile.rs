@@ -0,0 +1,378 @@
+use dust_dds::infrastructure::{
+    qos::{DataReaderQos, DataWriterQos, DomainParticipantQos, PublisherQos, SubscriberQos},
+    qos_policy::{
+        DurabilityQosPolicy, DurabilityQosPolicyKind, HistoryQosPolicy, HistoryQosPolicyKind,
+        PartitionQosPolicy, ReliabilityQosPolicy, ReliabilityQosPolicyKind,
+    },
+    time::{Duration, DurationKind},
+};
+use roxmltree::Node;
+
+/// QoS profile as defined by a `<qos_profile>` element of an OMG DDS-XML file.
+/// Only the entity QoS present in the file are set.
+#[derive(Clone, Default)]
+pub struct QosProfile {
+    pub name: String,
+    pub is_default: bool,
+    pub participant_qos: Option<DomainParticipantQos>,
+    pub publisher_qos: Option<PublisherQos>,
+    pub subscriber_qos: Option<SubscriberQos>,
+    pub datawriter_qos: Option<DataWriterQos>,
+    pub datareader_qos: Option<DataReaderQos>,
+}
+
+#[derive(Clone, Default)]
+pub struct QosProfileLibrary {
+    profiles: Vec<QosProfile>,
+}
+
+impl QosProfileLibrary {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let xml = std::fs::read_to_string(path)
+            .map_err(|e| format!("Couldn't read QoS file {}: {}", path, e))?;
+        Self::parse(&xml)
+    }
+
+    pub fn parse(xml: &str) -> Result<Self, String> {
+        let document = roxmltree::Document::parse(xml).map_err(|e| e.to_string())?;
+        let mut profiles = Vec::new();
+        for library in elements(document.root_element()).filter(|n| n.has_tag_name("qos_library")) {
+            let library_name = library.attribute("name").unwrap_or_default();
+            for profile in elements(library).filter(|n| n.has_tag_name("qos_profile")) {
+                profiles.push(parse_profile(library_name, profile)?);
+            }
+        }
+        Ok(Self { profiles })
+    }
+
+    /// Profile marked with `is_default_qos="true"`, used for the participant,
+    /// publisher and subscriber of the application.
+    pub fn default_profile(&self) -> Option<&QosProfile> {
+        self.profiles.iter().find(|p| p.is_default)
+    }
+
+    pub fn datawriter_profile_names(&self) -> Vec<String> {
+        self.profiles
+            .iter()
+            .filter(|p| p.datawriter_qos.is_some())
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    pub fn datareader_profile_names(&self) -> Vec<String> {
+        self.profiles
+            .iter()
+            .filter(|p| p.datareader_qos.is_some())
+            .map(|p| p.name.clone())
+            .collect()
+    }
+
+    pub fn datawriter_qos(&self, profile_name: &str) -> Option<DataWriterQos> {
+        self.profile(profile_name)?.datawriter_qos.clone()
+    }
+
+    pub fn datareader_qos(&self, profile_name: &str) -> Option<DataReaderQos> {
+        self.profile(profile_name)?.datareader_qos.clone()
+    }
+
+    fn profile(&self, profile_name: &str) -> Option<&QosProfile> {
+        self.profiles.iter().find(|p| p.name == profile_name)
+    }
+}
+
+fn elements<'a, 'input>(node: Node<'a, 'input>) -> impl Iterator<Item = Node<'a, 'input>> {
+    node.children().filter(|n| n.is_element())
+}
+
+fn child<'a, 'input>(node: Node<'a, 'input>, name: &str) -> Option<Node<'a, 'input>> {
+    elements(node).find(|n| n.has_tag_name(name))
+}
+
+fn child_text<'a>(node: Node<'a, '_>, name: &str) -> Option<&'a str> {
+    child(node, name).and_then(|n| n.text()).map(str::trim)
+}
+
+fn parse_number<T: std::str::FromStr>(node: Node, name: &str) -> Result<Option<T>, String> {
+    child_text(node, name)
+        .map(|text| {
+            text.parse()
+                .map_err(|_| format!("Invalid value \"{}\" for <{}>", text, name))
+        })
+        .transpose()
+}
+
+fn unsupported(node: Node) -> String {
+    format!("Unsupported QoS element <{}>", node.tag_name().name())
+}
+
+fn parse_profile(library_name: &str, node: Node) -> Result<QosProfile, String> {
+    let mut profile = QosProfile {
+        name: format!(
+            "{}::{}",
+            library_name,
+            node.attribute("name").unwrap_or_default()
+        ),
+        is_default: node.attribute("is_default_qos") == Some("true"),
+        ..Default::default()
+    };
+    for entity_qos in elements(node) {
+        match entity_qos.tag_name().name() {
+            "domain_participant_qos" => {
+                let mut qos = DomainParticipantQos::default();
+                parse_participant_qos(entity_qos, &mut qos)?;
+                profile.participant_qos = Some(qos);
+            }
+            "publisher_qos" => {
+                let mut qos = PublisherQos::default();
+                parse_publisher_qos(entity_qos, &mut qos)?;
+                profile.publisher_qos = Some(qos);
+            }
+            "subscriber_qos" => {
+                let mut qos = SubscriberQos::default();
+                parse_subscriber_qos(entity_qos, &mut qos)?;
+                profile.subscriber_qos = Some(qos);
+            }
+            "datawriter_qos" => {
+                let mut qos = DataWriterQos::default();
+                parse_datawriter_qos(entity_qos, &mut qos)?;
+                profile.datawriter_qos = Some(qos);
+            }
+            "datareader_qos" => {
+                let mut qos = DataReaderQos::default();
+                parse_datareader_qos(entity_qos, &mut qos)?;
+                profile.datareader_qos = Some(qos);
+            }
+            _ => return Err(unsupported(entity_qos)),
+        }
+    }
+    Ok(profile)
+}
+
+fn parse_participant_qos(node: Node, qos: &mut DomainParticipantQos) -> Result<(), String> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "entity_factory" => {
+                if let Some(autoenable) = child_text(policy, "autoenable_created_entities") {
+                    qos.entity_factory.autoenable_created_entities = autoenable == "true";
+                }
+            }
+            _ => return Err(unsupported(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_publisher_qos(node: Node, qos: &mut PublisherQos) -> Result<(), String> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "partition" => qos.partition = parse_partition(policy),
+            _ => return Err(unsupported(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_subscriber_qos(node: Node, qos: &mut SubscriberQos) -> Result<(), String> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "partition" => qos.partition = parse_partition(policy),
+            _ => return Err(unsupported(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_datawriter_qos(node: Node, qos: &mut DataWriterQos) -> Result<(), String> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "reliability" => parse_reliability(policy, &mut qos.reliability)?,
+            "durability" => parse_durability(policy, &mut qos.durability)?,
+            "history" => parse_history(policy, &mut qos.history)?,
+            "deadline" => {
+                if let Some(period) = child(policy, "period") {
+                    qos.deadline.period = parse_duration(period)?;
+                }
+            }
+            "lifespan" => {
+                if let Some(duration) = child(policy, "duration") {
+                    qos.lifespan.duration = parse_duration(duration)?;
+                }
+            }
+            _ => return Err(unsupported(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_datareader_qos(node: Node, qos: &mut DataReaderQos) -> Result<(), String> {
+    for policy in elements(node) {
+        match policy.tag_name().name() {
+            "reliability" => parse_reliability(policy, &mut qos.reliability)?,
+            "durability" => parse_durability(policy, &mut qos.durability)?,
+            "history" => parse_history(policy, &mut qos.history)?,
+            "deadline" => {
+                if let Some(period) = child(policy, "period") {
+                    qos.deadline.period = parse_duration(period)?;
+                }
+            }
+            _ => return Err(unsupported(policy)),
+        }
+    }
+    Ok(())
+}
+
+fn parse_duration(node: Node) -> Result<DurationKind, String> {
+    let sec = child_text(node, "sec").unwrap_or("0");
+    if sec == "DURATION_INFINITY_SEC" || sec == "DURATION_INFINITE_SEC" {
+        return Ok(DurationKind::Infinite);
+    }
+    let sec = sec
+        .parse()
+        .map_err(|_| format!("Invalid value \"{}\" for <sec>", sec))?;
+    let nanosec = parse_number(node, "nanosec")?.unwrap_or(0);
+    Ok(DurationKind::Finite(Duration::new(sec, nanosec)))
+}
+
+fn parse_reliability(node: Node, policy: &mut ReliabilityQosPolicy) -> Result<(), String> {
+    if let Some(kind) = child_text(node, "kind") {
+        policy.kind = match kind {
+            "BEST_EFFORT_RELIABILITY_QOS" => ReliabilityQosPolicyKind::BestEffort,
+            "RELIABLE_RELIABILITY_QOS" => ReliabilityQosPolicyKind::Reliable,
+            _ => return Err(format!("Invalid reliability kind \"{}\"", kind)),
+        };
+    }
+    if let Some(max_blocking_time) = child(node, "max_blocking_time") {
+        policy.max_blocking_time = parse_duration(max_blocking_time)?;
+    }
+    Ok(())
+}
+
+fn parse_durability(node: Node, policy: &mut DurabilityQosPolicy) -> Result<(), String> {
+    if let Some(kind) = child_text(node, "kind") {
+        policy.kind = match kind {
+            "VOLATILE_DURABILITY_QOS" => DurabilityQosPolicyKind::Volatile,
+            "TRANSIENT_LOCAL_DURABILITY_QOS" => DurabilityQosPolicyKind::TransientLocal,
+            "TRANSIENT_DURABILITY_QOS" | "PERSISTENT_DURABILITY_QOS" => {
+                return Err(format!(
+                    "Durability kind \"{}\" is not supported by Dust DDS",
+                    kind
+                ))
+            }
+            _ => return Err(format!("Invalid durability kind \"{}\"", kind)),
+        };
+    }
+    Ok(())
+}
+
+fn parse_history(node: Node, policy: &mut HistoryQosPolicy) -> Result<(), String> {
+    let depth = parse_number(node, "depth")?.unwrap_or(1);
+    policy.kind = match child_text(node, "kind").unwrap_or("KEEP_LAST_HISTORY_QOS") {
+        "KEEP_LAST_HISTORY_QOS" => HistoryQosPolicyKind::KeepLast(depth),
+        "KEEP_ALL_HISTORY_QOS" => HistoryQosPolicyKind::KeepAll,
+        kind => return Err(format!("Invalid history kind \"{}\"", kind)),
+    };
+    Ok(())
+}
+
+fn parse_partition(node: Node) -> PartitionQosPolicy {
+    PartitionQosPolicy {
+        name: child(node, "name")
+            .map(|names| {
+                elements(names)
+                    .filter_map(|element| element.text())
+                    .map(|name| name.trim().to_string())
+                    .collect()
+            })
+            .unwrap_or_default(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = include_str!("../res/qos_profiles.xml");
+
+    #[test]
+    fn parse_example_profiles() {
+        let library = QosProfileLibrary::parse(EXAMPLE).unwrap();
+        let default_profile = library.default_profile().unwrap();
+        assert_eq!(default_profile.name, "ShapesDemo::Default");
+        assert!(
+            default_profile
+                .participant_qos
+                .as_ref()
+                .unwrap()
+                .entity_factory
+                .autoenable_created_entities
+        );
+        assert_eq!(
+            library.datawriter_profile_names(),
+            [
+                "ShapesDemo::ReliableTransientLocal",
+                "ShapesDemo::BestEffort"
+            ]
+        );
+        assert_eq!(
+            library.datareader_profile_names(),
+            [
+                "ShapesDemo::ReliableTransientLocal",
+                "ShapesDemo::BestEffort"
+            ]
+        );
+    }
+
+    #[test]
+    fn parse_example_writer_qos() {
+        let library = QosProfileLibrary::parse(EXAMPLE).unwrap();
+        let qos = library
+            .datawriter_qos("ShapesDemo::ReliableTransientLocal")
+            .unwrap();
+        assert!(qos.reliability.kind == ReliabilityQosPolicyKind::Reliable);
+        assert!(qos.reliability.max_blocking_time == DurationKind::Finite(Duration::new(1, 0)));
+        assert!(qos.durability.kind == DurabilityQosPolicyKind::TransientLocal);
+        assert!(qos.history.kind == HistoryQosPolicyKind::KeepLast(5));
+    }
+
+    #[test]
+    fn example_deadlines_are_compatible() {
+        let library = QosProfileLibrary::parse(EXAMPLE).unwrap();
+        let writer_qos = library.datawriter_qos("ShapesDemo::BestEffort").unwrap();
+        let reader_qos = library.datareader_qos("ShapesDemo::BestEffort").unwrap();
+        // The offered deadline must not be longer than the requested one
+        assert!(writer_qos.deadline.period == reader_qos.deadline.period);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let profile = |qos: &str| {
+            format!(
+                "<dds><qos_library name=\"L\"><qos_profile name=\"P\">{}</qos_profile></qos_library></dds>",
+                qos
+            )
+        };
+        assert!(QosProfileLibrary::parse("<dds>").is_err());
+        assert_eq!(
+            QosProfileLibrary::parse(&profile("<topic_qos/>"))
+                .err()
+                .unwrap(),
+            "Unsupported QoS element <topic_qos>"
+        );
+        assert_eq!(
+            QosProfileLibrary::parse(&profile(
+                "<datareader_qos><reliability><kind>FAST</kind></reliability></datareader_qos>"
+            ))
+            .err()
+            .unwrap(),
+            "Invalid reliability kind \"FAST\""
+        );
+        assert_eq!(
+            QosProfileLibrary::parse(&profile(
+                "<datawriter_qos><history><depth>many</depth></history></datawriter_qos>"
+            ))
+            .err()
+            .unwrap(),
+            "Invalid value \"many\" for <depth>"
+        );
+    }
+}
@@ -0,0 +1,154 @@
+use std::{
+    collections::VecDeque,
+    time::{Duration, Instant, SystemTime, UNIX_EPOCH},
+};
+
+use dust_dds::infrastructure::time::Time;
+use eframe::egui;
+
+const WINDOW: Duration = Duration::from_secs(1);
+const HISTORY_LENGTH: usize = 30;
+
+#[derive(Clone)]
+pub struct ReaderStatistics {
+    window_start: Instant,
+    window_samples: u32,
+    window_latency_sum: f64,
+    samples_per_second: f32,
+    latency_ms: Option<f32>,
+    rate_history: VecDeque<f32>,
+}
+
+impl Default for ReaderStatistics {
+    fn default() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_samples: 0,
+            window_latency_sum: 0.0,
+            samples_per_second: 0.0,
+            latency_ms: None,
+            rate_history: VecDeque::with_capacity(HISTORY_LENGTH),
+        }
+    }
+}
+
+impl ReaderStatistics {
+    pub fn add_sample(&mut self, source_timestamp: Option<Time>) {
+        self.window_samples += 1;
+        if let Some(source_timestamp) = source_timestamp {
+            let source = source_timestamp.sec() as f64 + source_timestamp.nanosec() as f64 * 1e-9;
+            let reception = SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .unwrap_or_default()
+                .as_secs_f64();
+            self.window_latency_sum += reception - source;
+        }
+    }
+
+    /// Completes the current window once it is elapsed. Returns whether the
+    /// statistics changed.
+    pub fn update(&mut self) -> bool {
+        let elapsed = self.window_start.elapsed();
+        if elapsed < WINDOW {
+            return false;
+        }
+        self.samples_per_second = self.window_samples as f32 / elapsed.as_secs_f32();
+        self.latency_ms = if self.window_samples > 0 {
+            Some((self.window_latency_sum / self.window_samples as f64 * 1000.0) as f32)
+        } else {
+            None
+        };
+        if self.rate_history.len() == HISTORY_LENGTH {
+            self.rate_history.pop_front();
+        }
+        self.rate_history.push_back(self.samples_per_second);
+        self.window_start = Instant::now();
+        self.window_samples = 0;
+        self.window_latency_sum = 0.0;
+        true
+    }
+
+    pub fn samples_per_second(&self) -> f32 {
+        self.samples_per_second
+    }
+
+    pub fn latency_ms(&self) -> Option<f32> {
+        self.latency_ms
+    }
+
+    pub fn rate_history(&self) -> &VecDeque<f32> {
+        &self.rate_history
+    }
+}
+
+/// Counts events and reports their rate over the last completed window.
+pub struct RateMeter {
+    window_start: Instant,
+    window_count: usize,
+    rate: f32,
+}
+
+impl Default for RateMeter {
+    fn default() -> Self {
+        Self {
+            window_start: Instant::now(),
+            window_count: 0,
+            rate: 0.0,
+        }
+    }
+}
+
+impl RateMeter {
+    pub fn add(&mut self, count: usize) {
+        self.window_count += count;
+        let elapsed = self.window_start.elapsed();
+        if elapsed >= WINDOW {
+            self.rate = self.window_count as f32 / elapsed.as_secs_f32();
+            self.window_start = Instant::now();
+            self.window_count = 0;
+        }
+    }
+
+    pub fn rate(&self) -> f32 {
+        self.rate
+    }
+}
+
+pub struct Sparkline<'a> {
+    values: &'a VecDeque<f32>,
+    size: egui::Vec2,
+}
+
+impl<'a> Sparkline<'a> {
+    pub fn new(values: &'a VecDeque<f32>) -> Self {
+        Self {
+            values,
+            size: egui::vec2(80.0, 16.0),
+        }
+    }
+}
+
+impl<'a> egui::Widget for Sparkline<'a> {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        let (response, painter) = ui.allocate_painter(self.size, egui::Sense::hover());
+        let rect = response.rect;
+        let max = self.values.iter().cloned().fold(1.0, f32::max);
+        let step = rect.width() / (HISTORY_LENGTH - 1) as f32;
+        let points = self
+            .values
+            .iter()
+            .enumerate()
+            .map(|(i, value)| {
+                egui::pos2(
+                    rect.left() + i as f32 * step,
+                    rect.bottom() - value / max * rect.height(),
+                )
+            })
+            .collect();
+        painter.add(egui::Shape::line(
+            points,
+            egui::Stroke::new(1.0, ui.visuals().text_color()),
+        ));
+        response
+    }
+}
@@ -0,0 +1,260 @@
+use std::{
+    collections::{BTreeMap, HashMap},
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Arc, Mutex, MutexGuard, OnceLock, PoisonError,
+    },
+    time::Instant,
+};
+
+use dust_dds::{
+    dds_async::{
+        data_reader::DataReaderAsync,
+        wait_set::{ConditionAsync, WaitSetAsync},
+    },
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        instance::InstanceHandle,
+        qos_policy::ReaderDataLifecycleQosPolicy,
+        status::StatusKind,
+        time::{Duration, DurationKind},
+    },
+    subscription::sample_info::{
+        InstanceStateKind, SampleStateKind, ANY_INSTANCE_STATE, ANY_SAMPLE_STATE, ANY_VIEW_STATE,
+    },
+};
+use eframe::egui;
+
+use super::{
+    app::shapes_type::ShapeType, reader_statistics::ReaderStatistics, shapes_widget::GuiShape,
+};
+
+#[derive(Default)]
+pub struct ReaderScene {
+    pub shapes: Vec<GuiShape>,
+    pub statistics: ReaderStatistics,
+}
+
+fn finite_delay(delay: &DurationKind) -> Option<std::time::Duration> {
+    match delay {
+        DurationKind::Finite(delay) => Some(std::time::Duration::new(
+            delay.sec().max(0) as u64,
+            delay.nanosec(),
+        )),
+        DurationKind::Infinite => None,
+    }
+}
+
+/// Instances that are not alive are purged by the reader after its autopurge
+/// delays, without new data being available. The tracker tells until when
+/// the reader has to be read again to see the purges. Instances are never
+/// purged with an infinite delay.
+struct PurgeTracker {
+    no_writers_delay: Option<std::time::Duration>,
+    disposed_delay: Option<std::time::Duration>,
+    deadline: Option<Instant>,
+}
+
+impl PurgeTracker {
+    fn new(reader_data_lifecycle: &ReaderDataLifecycleQosPolicy) -> Self {
+        Self {
+            no_writers_delay: finite_delay(&reader_data_lifecycle.autopurge_nowriter_samples_delay),
+            disposed_delay: finite_delay(&reader_data_lifecycle.autopurge_disposed_samples_delay),
+            deadline: None,
+        }
+    }
+
+    /// The purge delay of an instance starts when it stops being alive.
+    fn instance_state_changed(&mut self, instance_state: InstanceStateKind) {
+        let delay = match instance_state {
+            InstanceStateKind::Alive => None,
+            InstanceStateKind::NotAliveDisposed => self.disposed_delay,
+            InstanceStateKind::NotAliveNoWriters => self.no_writers_delay,
+        };
+        if let Some(delay) = delay {
+            let deadline = Instant::now() + delay;
+            self.deadline = Some(self.deadline.map_or(deadline, |d| d.max(deadline)));
+        }
+    }
+}
+
+/// Background task collecting the samples of a reader into a scene as soon
+/// as the DataAvailable status of the reader is triggered. The task runs on
+/// the runtime it is started from and is aborted when it is dropped.
+pub struct ReaderTask {
+    scene: Arc<Mutex<ReaderScene>>,
+    task: tokio::task::JoinHandle<()>,
+}
+
+impl ReaderTask {
+    pub async fn start(
+        reader: DataReaderAsync<ShapeType>,
+        drained_samples: Arc<AtomicUsize>,
+        repaint_context: Arc<OnceLock<egui::Context>>,
+    ) -> DdsResult<Self> {
+        let mut purge_tracker = PurgeTracker::new(&reader.get_qos().await?.reader_data_lifecycle);
+        let status_condition = reader.get_statuscondition();
+        status_condition
+            .set_enabled_statuses(&[StatusKind::DataAvailable])
+            .await?;
+        let mut wait_set = WaitSetAsync::new();
+        wait_set
+            .attach_condition(ConditionAsync::StatusCondition(status_condition))
+            .await?;
+
+        let scene = Arc::new(Mutex::new(ReaderScene::default()));
+        let scene_clone = scene.clone();
+        let task = tokio::spawn(async move {
+            let timeout = Duration::new(0, 100_000_000);
+            let kind = reader.get_topicdescription().get_name();
+            let mut instances = BTreeMap::new();
+            let mut statistics = ReaderStatistics::default();
+            loop {
+                let is_data_available = wait_set.wait(timeout).await.is_ok();
+                if is_data_available {
+                    let count = drain_reader(
+                        &reader,
+                        &kind,
+                        &mut instances,
+                        &mut statistics,
+                        &mut purge_tracker,
+                    )
+                    .await;
+                    drained_samples.fetch_add(count, Ordering::Relaxed);
+                }
+                let is_purge_pending = purge_tracker.deadline.is_some();
+                if let Some(deadline) = purge_tracker.deadline {
+                    let now = Instant::now();
+                    remove_purged_instances(&reader, &mut instances, &mut purge_tracker).await;
+                    // The first read after the deadline is the last one needed
+                    if deadline < now && purge_tracker.deadline == Some(deadline) {
+                        purge_tracker.deadline = None;
+                    }
+                }
+                let is_statistics_updated = statistics.update();
+                if is_data_available || is_purge_pending || is_statistics_updated {
+                    // The scene is built before locking, so that the GUI never
+                    // waits for the reader
+                    let shapes = instances.values().cloned().collect();
+                    *lock_scene(&scene_clone) = ReaderScene {
+                        shapes,
+                        statistics: statistics.clone(),
+                    };
+                    if let Some(ctx) = repaint_context.get() {
+                        ctx.request_repaint();
+                    }
+                }
+            }
+        });
+
+        Ok(Self { scene, task })
+    }
+
+    pub fn scene(&self) -> MutexGuard<'_, ReaderScene> {
+        lock_scene(&self.scene)
+    }
+}
+
+impl Drop for ReaderTask {
+    fn drop(&mut self) {
+        self.task.abort();
+    }
+}
+
+/// The scene is only replaced as a whole, so it is consistent even if the
+/// task panicked while holding the lock.
+fn lock_scene(scene: &Mutex<ReaderScene>) -> MutexGuard<'_, ReaderScene> {
+    scene.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Reads the samples received since the last drain with a single read call
+/// and keeps the latest shape of each instance. Returns the number of samples
+/// read.
+async fn drain_reader(
+    reader: &DataReaderAsync<ShapeType>,
+    kind: &str,
+    instances: &mut BTreeMap<InstanceHandle, GuiShape>,
+    statistics: &mut ReaderStatistics,
+    purge_tracker: &mut PurgeTracker,
+) -> usize {
+    let Ok(samples) = reader
+        .read(
+            i32::MAX,
+            &[SampleStateKind::NotRead],
+            ANY_VIEW_STATE,
+            ANY_INSTANCE_STATE,
+        )
+        .await
+    else {
+        return 0;
+    };
+    for sample in samples.iter() {
+        let sample_info = sample.sample_info();
+        statistics.add_sample(sample_info.source_timestamp);
+        let previous_state = instances
+            .get(&sample_info.instance_handle)
+            .map(GuiShape::instance_state);
+        if previous_state != Some(sample_info.instance_state) {
+            purge_tracker.instance_state_changed(sample_info.instance_state);
+        }
+        // Samples without valid data notify that the instance was disposed
+        // or unregistered, so it is drawn with its last shape until it is
+        // purged
+        match sample.data() {
+            Ok(shape_type) => {
+                let mut shape = GuiShape::from_shape_type(kind.to_string(), &shape_type);
+                shape.set_instance_state(sample_info.instance_state);
+                instances.insert(sample_info.instance_handle, shape);
+            }
+            Err(_) => {
+                if let Some(shape) = instances.get_mut(&sample_info.instance_handle) {
+                    shape.set_instance_state(sample_info.instance_state);
+                }
+            }
+        }
+    }
+    samples.len()
+}
+
+/// Reads the instances that are not alive and removes the ones the reader
+/// has purged. The samples of these instances are read whatever their sample
+/// state, so their instance state is updated here as well.
+async fn remove_purged_instances(
+    reader: &DataReaderAsync<ShapeType>,
+    instances: &mut BTreeMap<InstanceHandle, GuiShape>,
+    purge_tracker: &mut PurgeTracker,
+) {
+    let samples = match reader
+        .read(
+            i32::MAX,
+            ANY_SAMPLE_STATE,
+            ANY_VIEW_STATE,
+            &[
+                InstanceStateKind::NotAliveDisposed,
+                InstanceStateKind::NotAliveNoWriters,
+            ],
+        )
+        .await
+    {
+        Ok(samples) => samples,
+        Err(DdsError::NoData) => Vec::new(),
+        Err(_) => return,
+    };
+    let not_alive_states: HashMap<_, _> = samples
+        .iter()
+        .map(|sample| {
+            let sample_info = sample.sample_info();
+            (sample_info.instance_handle, sample_info.instance_state)
+        })
+        .collect();
+    instances.retain(|handle, shape| match not_alive_states.get(handle) {
+        Some(instance_state) => {
+            if shape.instance_state() != *instance_state {
+                purge_tracker.instance_state_changed(*instance_state);
+                shape.set_instance_state(*instance_state);
+            }
+            true
+        }
+        None => shape.instance_state() == InstanceStateKind::Alive,
+    });
+}
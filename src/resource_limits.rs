@@ -0,0 +1,68 @@
+use dust_dds::infrastructure::qos_policy::{Length, ResourceLimitsQosPolicy};
+use eframe::egui;
+use serde::{Deserialize, Serialize};
+
+/// Resource limits of a writer or reader, unlimited if not set.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ResourceLimitsSettings {
+    pub max_samples: Option<u32>,
+    pub max_instances: Option<u32>,
+    pub max_samples_per_instance: Option<u32>,
+}
+
+fn length(limit: Option<u32>) -> Length {
+    match limit {
+        Some(limit) => Length::Limited(limit),
+        None => Length::Unlimited,
+    }
+}
+
+impl ResourceLimitsSettings {
+    pub fn is_unlimited(&self) -> bool {
+        self.max_samples.is_none()
+            && self.max_instances.is_none()
+            && self.max_samples_per_instance.is_none()
+    }
+
+    /// Sets the limits of the policy, keeping the limits of the QoS profile
+    /// unless some are set.
+    pub fn apply(&self, policy: &mut ResourceLimitsQosPolicy) {
+        if self.is_unlimited() {
+            return;
+        }
+        *policy = ResourceLimitsQosPolicy {
+            max_samples: length(self.max_samples),
+            max_instances: length(self.max_instances),
+            max_samples_per_instance: length(self.max_samples_per_instance),
+        };
+    }
+}
+
+fn limit_ui(ui: &mut egui::Ui, text: &str, limit: &mut Option<u32>) {
+    ui.horizontal(|ui| {
+        let mut is_limited = limit.is_some();
+        ui.checkbox(&mut is_limited, text);
+        if is_limited {
+            let limit = limit.get_or_insert(1);
+            ui.add(egui::DragValue::new(limit).clamp_range(1..=1000));
+        } else {
+            *limit = None;
+        }
+    });
+}
+
+impl egui::Widget for &mut ResourceLimitsSettings {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            limit_ui(ui, "max samples", &mut self.max_samples);
+            limit_ui(ui, "max instances", &mut self.max_instances);
+            limit_ui(
+                ui,
+                "max samples per instance",
+                &mut self.max_samples_per_instance,
+            );
+        })
+        .response
+    }
+}
@@ -0,0 +1,173 @@
+use serde::{Deserialize, Serialize};
+
+use super::{impairment::ImpairmentSettings, resource_limits::ResourceLimitsSettings};
+
+pub const DEFAULT_POSITION: [f32; 2] = [100.0, 80.0];
+pub const DEFAULT_WORLD_SIZE: [f32; 2] = [235.0, 265.0];
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct WriterSettings {
+    /// Name of the writer shown in the entity grid.
+    pub name: String,
+    pub topic_name: String,
+    pub color: String,
+    pub is_reliable: bool,
+    pub qos_profile: Option<String>,
+    /// Latency budget in milliseconds, the QoS default is kept if 0.
+    pub latency_budget_ms: u32,
+    /// Transport priority, the QoS default is kept if 0.
+    pub transport_priority: i32,
+    /// Dispose the instances of the writer when they are unregistered.
+    pub autodispose_unregistered_instances: bool,
+    /// Spawn position, chosen randomly within the world if not set.
+    pub position: Option<[f32; 2]>,
+    pub velocity: [f32; 2],
+    pub size: f32,
+    pub is_pulsing: bool,
+    pub instance_count: usize,
+    pub impairment: ImpairmentSettings,
+    pub resource_limits: ResourceLimitsSettings,
+}
+
+impl Default for WriterSettings {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            topic_name: "Square".to_string(),
+            color: "BLUE".to_string(),
+            is_reliable: false,
+            qos_profile: None,
+            latency_budget_ms: 0,
+            transport_priority: 0,
+            autodispose_unregistered_instances: true,
+            position: Some(DEFAULT_POSITION),
+            velocity: [30.0, 20.0],
+            size: 30.0,
+            is_pulsing: false,
+            instance_count: 1,
+            impairment: ImpairmentSettings::default(),
+            resource_limits: ResourceLimitsSettings::default(),
+        }
+    }
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ReaderSettings {
+    /// Name of the reader shown in the entity grid.
+    pub name: String,
+    pub topic_name: String,
+    pub is_reliable: bool,
+    pub qos_profile: Option<String>,
+    /// Latency budget in milliseconds, the QoS default is kept if 0.
+    pub latency_budget_ms: u32,
+    /// Delays in milliseconds after which the instances without writers and
+    /// the disposed instances are purged, never if not set.
+    pub autopurge_nowriter_samples_delay_ms: Option<u32>,
+    pub autopurge_disposed_samples_delay_ms: Option<u32>,
+    pub resource_limits: ResourceLimitsSettings,
+}
+
+impl Default for ReaderSettings {
+    fn default() -> Self {
+        Self {
+            name: String::new(),
+            topic_name: "Square".to_string(),
+            is_reliable: false,
+            qos_profile: None,
+            latency_budget_ms: 0,
+            autopurge_nowriter_samples_delay_ms: None,
+            autopurge_disposed_samples_delay_ms: None,
+            resource_limits: ResourceLimitsSettings::default(),
+        }
+    }
+}
+
+/// Domain participant of a scenario with its writers and readers.
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct ParticipantSettings {
+    pub domain_id: i32,
+    pub name: String,
+    pub writers: Vec<WriterSettings>,
+    pub readers: Vec<ReaderSettings>,
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Scenario {
+    pub publish_rate_ms: u64,
+    pub world_size: [f32; 2],
+    pub participants: Vec<ParticipantSettings>,
+}
+
+impl Default for Scenario {
+    fn default() -> Self {
+        Self {
+            publish_rate_ms: 25,
+            world_size: DEFAULT_WORLD_SIZE,
+            participants: vec![ParticipantSettings::default()],
+        }
+    }
+}
+
+impl Scenario {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let json = std::fs::read_to_string(path)
+            .map_err(|e| format!("Couldn't read scenario file {}: {}", path, e))?;
+        serde_json::from_str(&json).map_err(|e| format!("Invalid scenario file {}: {}", path, e))
+    }
+
+    pub fn save(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| e.to_string())?;
+        std::fs::write(path, json)
+            .map_err(|e| format!("Couldn't write scenario file {}: {}", path, e))
+    }
+}
+
+pub struct ScenarioWidget {
+    pub path: String,
+    pub message: Option<String>,
+    pub save_clicked: bool,
+    pub load_clicked: bool,
+    pub run_timeline_clicked: bool,
+}
+
+impl Default for ScenarioWidget {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ScenarioWidget {
+    pub fn new() -> Self {
+        Self {
+            path: "scenario.json".to_string(),
+            message: None,
+            save_clicked: false,
+            load_clicked: false,
+            run_timeline_clicked: false,
+        }
+    }
+}
+
+impl eframe::egui::Widget for &mut ScenarioWidget {
+    fn ui(self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
+        ui.vertical(|ui| {
+            ui.horizontal(|ui| {
+                ui.label("File:");
+                ui.text_edit_singleline(&mut self.path);
+            });
+            ui.horizontal(|ui| {
+                self.save_clicked = ui.button("Save").clicked();
+                self.load_clicked = ui.button("Load").clicked();
+                self.run_timeline_clicked = ui.button("Run timeline").clicked();
+            });
+            if let Some(message) = &self.message {
+                ui.label(message);
+            }
+        })
+        .response
+    }
+}
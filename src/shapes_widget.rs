@@ -1,3 +1,4 @@
+use dust_dds::subscription::sample_info::InstanceStateKind;
 use eframe::egui::{self};
 
 use super::app::shapes_type::ShapeType;
@@ -11,57 +12,135 @@ const CYAN: egui::Color32 = egui::Color32::from_rgb(0, 255, 255);
 const MAGENTA: egui::Color32 = egui::Color32::from_rgb(255, 0, 255);
 const ORANGE: egui::Color32 = egui::Color32::from_rgb(255, 165, 0);
 
+const PULSE_AMPLITUDE: f32 = 0.5;
+const PULSE_PERIOD: f32 = 2.0;
+
+#[derive(Clone, Copy)]
+pub enum Glyph {
+    Circle,
+    Triangle,
+    Square,
+    Star,
+    Hexagon,
+    Ellipse,
+}
+
+/// Glyph used to draw the shapes of each topic. Topics that are not listed
+/// are drawn as circles.
+pub const SHAPE_KINDS: &[(&str, Glyph)] = &[
+    ("Square", Glyph::Square),
+    ("Circle", Glyph::Circle),
+    ("Triangle", Glyph::Triangle),
+    ("Star", Glyph::Star),
+    ("Hexagon", Glyph::Hexagon),
+    ("Ellipse", Glyph::Ellipse),
+];
+
+fn glyph(kind: &str) -> Glyph {
+    SHAPE_KINDS
+        .iter()
+        .find(|(name, _)| *name == kind)
+        .map_or(Glyph::Circle, |(_, glyph)| *glyph)
+}
+
+fn polygon_points(center: egui::Pos2, radius: egui::Vec2, count: usize) -> Vec<egui::Pos2> {
+    (0..count)
+        .map(|i| {
+            let angle =
+                i as f32 / count as f32 * std::f32::consts::TAU - std::f32::consts::FRAC_PI_2;
+            center + egui::vec2(radius.x * angle.cos(), radius.y * angle.sin())
+        })
+        .collect()
+}
+
+/// Parses the named colors of the shapes demo and hexadecimal RGB colors like "#12AB34".
+fn parse_color(color: &str) -> Option<egui::Color32> {
+    let color = match color {
+        "PURPLE" => PURPLE,
+        "BLUE" => BLUE,
+        "RED" => RED,
+        "GREEN" => GREEN,
+        "YELLOW" => YELLOW,
+        "CYAN" => CYAN,
+        "MAGENTA" => MAGENTA,
+        "ORANGE" => ORANGE,
+        _ => {
+            let hex = color.strip_prefix('#').filter(|hex| hex.len() == 6)?;
+            let channel = |i: usize| u8::from_str_radix(hex.get(i..i + 2)?, 16).ok();
+            egui::Color32::from_rgb(channel(0)?, channel(2)?, channel(4)?)
+        }
+    };
+    Some(color)
+}
+
+pub fn hex_color(rgb: [u8; 3]) -> String {
+    format!("#{:02X}{:02X}{:02X}", rgb[0], rgb[1], rgb[2])
+}
+
 #[derive(Clone)]
 pub struct GuiShape {
     kind: String,
+    color_name: String,
     color: egui::Color32,
     position: egui::Pos2,
     size: f32,
+    instance_state: InstanceStateKind,
 }
 
 impl GuiShape {
     pub fn from_shape_type(kind: String, shape_type: &ShapeType) -> Self {
-        let color = match shape_type.color.as_str() {
-            "PURPLE" => PURPLE,
-            "BLUE" => BLUE,
-            "RED" => RED,
-            "GREEN" => GREEN,
-            "YELLOW" => YELLOW,
-            "CYAN" => CYAN,
-            "MAGENTA" => MAGENTA,
-            "ORANGE" => ORANGE,
-            _ => panic!("color not supported"),
-        };
+        let color = parse_color(&shape_type.color).unwrap_or(egui::Color32::GRAY);
         Self {
             kind,
+            color_name: shape_type.color.clone(),
             color,
             position: egui::pos2(shape_type.x as f32, shape_type.y as f32),
             size: shape_type.shapesize as f32,
+            instance_state: InstanceStateKind::Alive,
+        }
+    }
+
+    /// Instances that are not alive are drawn faded, and crossed out if they
+    /// were disposed.
+    pub fn set_instance_state(&mut self, instance_state: InstanceStateKind) {
+        self.instance_state = instance_state;
+    }
+
+    pub fn instance_state(&self) -> InstanceStateKind {
+        self.instance_state
+    }
+
+    fn fill(&self) -> egui::Color32 {
+        match self.instance_state {
+            InstanceStateKind::Alive => self.color,
+            _ => self.color.gamma_multiply(0.3),
         }
     }
 
+    /// Diagonals crossing out a disposed shape.
+    fn cross(&self, position: egui::Pos2, size: f32) -> Option<[[egui::Pos2; 2]; 2]> {
+        if self.instance_state != InstanceStateKind::NotAliveDisposed {
+            return None;
+        }
+        let half_size = egui::vec2(size / 2.0, size / 2.0);
+        Some([
+            [position - half_size, position + half_size],
+            [
+                position + egui::vec2(-half_size.x, half_size.y),
+                position + egui::vec2(half_size.x, -half_size.y),
+            ],
+        ])
+    }
+
     pub fn as_shape_type(&self) -> ShapeType {
-        let color = match self.color {
-            PURPLE => "PURPLE",
-            BLUE => "BLUE",
-            RED => "RED",
-            GREEN => "GREEN",
-            YELLOW => "YELLOW",
-            CYAN => "CYAN",
-            MAGENTA => "MAGENTA",
-            ORANGE => "ORANGE",
-            _ => panic!("Color not valid"),
-        }
-        .to_string();
         ShapeType {
-            color,
+            color: self.color_name.clone(),
             x: self.position.x as i32,
             y: self.position.y as i32,
             shapesize: self.size as i32,
         }
     }
 
-
     pub fn as_egui_shape(&self, scale: f32) -> egui::Shape {
         let stroke = egui::Stroke {
             width: 0.5,
@@ -70,51 +149,198 @@ impl GuiShape {
 
         let position = self.position * scale;
         let size = self.size * scale;
+        let fill = self.fill();
 
-        match self.kind.as_str() {
-            "Circle" => egui::epaint::CircleShape {
+        let shape = match glyph(&self.kind) {
+            Glyph::Circle => egui::epaint::CircleShape {
                 center: position,
                 radius: size / 2.0,
-                fill: self.color,
+                fill,
                 stroke,
             }
             .into(),
-            "Triangle" => egui::epaint::PathShape {
+            Glyph::Triangle => egui::epaint::PathShape {
                 points: vec![
                     position + egui::vec2(0.0, -size / 2.0),
                     position + egui::vec2(-size / 2.0, size / 2.0),
                     position + egui::vec2(size / 2.0, size / 2.0),
                 ],
                 closed: true,
-                fill: self.color,
+                fill,
                 stroke,
             }
             .into(),
-            "Square" => egui::epaint::RectShape::new(
+            Glyph::Square => egui::epaint::RectShape::new(
                 egui::Rect::from_center_size(position, egui::epaint::vec2(size, size)),
                 egui::Rounding::ZERO,
-                self.color,
+                fill,
                 stroke,
             )
             .into(),
-            _ => panic!("shape kind not valid")
+            Glyph::Hexagon => egui::epaint::PathShape::convex_polygon(
+                polygon_points(position, egui::vec2(size / 2.0, size / 2.0), 6),
+                fill,
+                stroke,
+            )
+            .into(),
+            Glyph::Ellipse => egui::epaint::PathShape::convex_polygon(
+                polygon_points(position, egui::vec2(size / 2.0, size / 3.0), 32),
+                fill,
+                stroke,
+            )
+            .into(),
+            Glyph::Star => {
+                let points: Vec<_> =
+                    polygon_points(position, egui::vec2(size / 2.0, size / 2.0), 10)
+                        .into_iter()
+                        .enumerate()
+                        .map(|(i, point)| {
+                            if i % 2 == 0 {
+                                point
+                            } else {
+                                position + (point - position) * 0.4
+                            }
+                        })
+                        .collect();
+                // A star is not convex, so it is filled as a triangle fan around its center
+                let mut mesh = egui::Mesh::default();
+                mesh.colored_vertex(position, fill);
+                for point in points.iter() {
+                    mesh.colored_vertex(*point, fill);
+                }
+                for i in 0..points.len() as u32 {
+                    mesh.add_triangle(0, i + 1, (i + 1) % points.len() as u32 + 1);
+                }
+                egui::Shape::Vec(vec![
+                    mesh.into(),
+                    egui::epaint::PathShape::closed_line(points, stroke).into(),
+                ])
+            }
+        };
+
+        match self.cross(position, size) {
+            Some(cross) => {
+                let cross_stroke = egui::Stroke::new(2.0, egui::Color32::BLACK);
+                let mut shapes = vec![shape];
+                shapes.extend(
+                    cross
+                        .into_iter()
+                        .map(|line| egui::Shape::line_segment(line, cross_stroke)),
+                );
+                egui::Shape::Vec(shapes)
+            }
+            None => shape,
         }
     }
 
+    /// Outline of the glyph of the shape around its center.
+    fn outline(&self, position: egui::Pos2, size: f32) -> Vec<egui::Pos2> {
+        let radius = egui::vec2(size / 2.0, size / 2.0);
+        match glyph(&self.kind) {
+            Glyph::Circle => polygon_points(position, radius, 32),
+            Glyph::Triangle => vec![
+                position + egui::vec2(0.0, -size / 2.0),
+                position + egui::vec2(size / 2.0, size / 2.0),
+                position + egui::vec2(-size / 2.0, size / 2.0),
+            ],
+            Glyph::Square => vec![
+                position - radius,
+                position + egui::vec2(radius.x, -radius.y),
+                position + radius,
+                position + egui::vec2(-radius.x, radius.y),
+            ],
+            Glyph::Hexagon => polygon_points(position, radius, 6),
+            Glyph::Ellipse => polygon_points(position, egui::vec2(size / 2.0, size / 3.0), 32),
+            Glyph::Star => polygon_points(position, radius, 10)
+                .into_iter()
+                .enumerate()
+                .map(|(i, point)| {
+                    if i % 2 == 0 {
+                        point
+                    } else {
+                        position + (point - position) * 0.4
+                    }
+                })
+                .collect(),
+        }
+    }
+
+    /// Adds the fill and the outline of the shape to a mesh shared by all the
+    /// shapes. Unlike [`Self::as_egui_shape`] the edges are not anti-aliased.
+    fn add_to_mesh(&self, mesh: &mut egui::Mesh, scale: f32, offset: egui::Vec2) {
+        let position = self.position * scale + offset;
+        let size = self.size * scale;
+        let outline = self.outline(position, size);
+
+        // All the glyphs are star-shaped around their center (the star is not
+        // convex), so they are filled as a triangle fan around it
+        let fill = self.fill();
+        let center = mesh.vertices.len() as u32;
+        mesh.colored_vertex(position, fill);
+        for point in outline.iter() {
+            mesh.colored_vertex(*point, fill);
+        }
+        let count = outline.len() as u32;
+        for i in 0..count {
+            mesh.add_triangle(center, center + i + 1, center + (i + 1) % count + 1);
+        }
+        for (i, point) in outline.iter().enumerate() {
+            let next = outline[(i + 1) % outline.len()];
+            add_line(mesh, *point, next, 0.5, egui::Color32::BLACK);
+        }
+        for [from, to] in self.cross(position, size).into_iter().flatten() {
+            add_line(mesh, from, to, 2.0, egui::Color32::BLACK);
+        }
+    }
+}
+
+/// Adds a line as a quad to the mesh.
+fn add_line(
+    mesh: &mut egui::Mesh,
+    from: egui::Pos2,
+    to: egui::Pos2,
+    width: f32,
+    color: egui::Color32,
+) {
+    if from == to {
+        return;
+    }
+    let normal = (to - from).normalized().rot90() * width / 2.0;
+    let index = mesh.vertices.len() as u32;
+    mesh.colored_vertex(from + normal, color);
+    mesh.colored_vertex(from - normal, color);
+    mesh.colored_vertex(to + normal, color);
+    mesh.colored_vertex(to - normal, color);
+    mesh.add_triangle(index, index + 1, index + 2);
+    mesh.add_triangle(index + 1, index + 3, index + 2);
 }
 
 #[derive(Clone)]
 pub struct MovingShapeObject {
     gui_shape: GuiShape,
     velocity: egui::Vec2,
+    base_size: f32,
+    is_pulsing: bool,
+    pulse_time: f32,
 }
 
 impl MovingShapeObject {
-    pub fn new(shape: GuiShape, velocity: egui::Vec2) -> Self {
-        Self { gui_shape: shape, velocity }
+    pub fn new(shape: GuiShape, velocity: egui::Vec2, is_pulsing: bool) -> Self {
+        Self {
+            base_size: shape.size,
+            gui_shape: shape,
+            velocity,
+            is_pulsing,
+            pulse_time: 0.0,
+        }
     }
 
     pub fn move_within_rect(&mut self, rect_size: egui::Vec2, time_delta: f32) {
+        if self.is_pulsing {
+            self.pulse_time = (self.pulse_time + time_delta) % PULSE_PERIOD;
+            let phase = self.pulse_time / PULSE_PERIOD * std::f32::consts::TAU;
+            self.gui_shape.size = self.base_size * (1.0 + PULSE_AMPLITUDE * phase.sin());
+        }
         let radius = self.gui_shape.size / 2.0;
         let rect = egui::Rect::from_min_size(egui::Pos2::ZERO, rect_size);
         // Inset rect to account for shape size
@@ -150,6 +376,7 @@ impl MovingShapeObject {
 pub struct ShapesWidget<'a> {
     original_size: egui::Vec2,
     shape_list: &'a [GuiShape],
+    is_batched: bool,
 }
 
 impl<'a> ShapesWidget<'a> {
@@ -157,9 +384,17 @@ impl<'a> ShapesWidget<'a> {
         Self {
             original_size,
             shape_list,
+            is_batched: false,
         }
     }
 
+    /// Draws all the shapes with a single mesh, which keeps thousands of
+    /// shapes cheap to tessellate at the cost of anti-aliasing.
+    pub fn batched(mut self, is_batched: bool) -> Self {
+        self.is_batched = is_batched;
+        self
+    }
+
     fn paint_area_and_shapes(&self, ui: &mut egui::Ui) -> egui::Response {
         let max_size = ui.max_rect().size();
         let scale = if self.original_size.y / self.original_size.x > max_size.y / max_size.x {
@@ -170,17 +405,25 @@ impl<'a> ShapesWidget<'a> {
         let desired_size = self.original_size * scale;
         let (response, painter) = ui.allocate_painter(desired_size, egui::Sense::hover());
         painter.rect_filled(response.rect, egui::Rounding::ZERO, egui::Color32::WHITE);
-        for shape in self.shape_list {
-            let mut shape = shape.as_egui_shape(scale);
-            shape.translate(response.rect.left_top().to_vec2());
-            painter.add(shape);
+        let offset = response.rect.left_top().to_vec2();
+        if self.is_batched {
+            let mut mesh = egui::Mesh::default();
+            for shape in self.shape_list {
+                shape.add_to_mesh(&mut mesh, scale, offset);
+            }
+            painter.add(mesh);
+        } else {
+            for shape in self.shape_list {
+                let mut shape = shape.as_egui_shape(scale);
+                shape.translate(offset);
+                painter.add(shape);
+            }
         }
 
         response
     }
 }
 
-
 impl<'a> egui::Widget for ShapesWidget<'a> {
     fn ui(self, ui: &mut eframe::egui::Ui) -> eframe::egui::Response {
         if ui.max_rect().width() / ui.max_rect().height() > 1.0 {
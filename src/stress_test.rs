@@ -0,0 +1,66 @@
+use eframe::egui;
+
+use super::shapes_widget::SHAPE_KINDS;
+
+pub struct StressTestWidget {
+    pub topic_name: String,
+    pub writer_count: usize,
+    pub instances_per_writer: usize,
+    pub start_clicked: bool,
+    pub stop_clicked: bool,
+    pub publish_throughput: f32,
+    pub drain_rate: f32,
+    pub frame_time_ms: f32,
+}
+
+impl Default for StressTestWidget {
+    fn default() -> Self {
+        Self {
+            topic_name: "Circle".to_string(),
+            writer_count: 10,
+            instances_per_writer: 100,
+            start_clicked: false,
+            stop_clicked: false,
+            publish_throughput: 0.0,
+            drain_rate: 0.0,
+            frame_time_ms: 0.0,
+        }
+    }
+}
+
+impl egui::Widget for &mut StressTestWidget {
+    fn ui(self, ui: &mut egui::Ui) -> egui::Response {
+        ui.vertical(|ui| {
+            egui::ComboBox::from_label("Topic")
+                .selected_text(self.topic_name.as_str())
+                .show_ui(ui, |ui| {
+                    for (kind, _) in SHAPE_KINDS {
+                        ui.selectable_value(&mut self.topic_name, kind.to_string(), *kind);
+                    }
+                });
+            ui.add(egui::Slider::new(&mut self.writer_count, 1..=100).text("writers"));
+            ui.add(
+                egui::Slider::new(&mut self.instances_per_writer, 1..=1000)
+                    .logarithmic(true)
+                    .text("instances per writer"),
+            );
+            ui.horizontal(|ui| {
+                self.start_clicked = ui.button("Start").clicked();
+                self.stop_clicked = ui.button("Stop").clicked();
+            });
+            ui.separator();
+            egui::Grid::new("stress_test_metrics").show(ui, |ui| {
+                ui.label("Publish throughput [samples/s]:");
+                ui.label(format!("{:.0}", self.publish_throughput));
+                ui.end_row();
+                ui.label("Reader drain rate [samples/s]:");
+                ui.label(format!("{:.0}", self.drain_rate));
+                ui.end_row();
+                ui.label("Frame time [ms]:");
+                ui.label(format!("{:.1}", self.frame_time_ms));
+                ui.end_row();
+            });
+        })
+        .response
+    }
+}
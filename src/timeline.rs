@@ -0,0 +1,250 @@
+use std::time::{Duration, Instant};
+
+use super::scenario::{ReaderSettings, WriterSettings};
+
+#[derive(Clone)]
+pub enum Action {
+    CreateWriter(WriterSettings),
+    CreateReader(ReaderSettings),
+    DeleteWriter {
+        topic_name: Option<String>,
+        color: Option<String>,
+    },
+    DeleteReader {
+        topic_name: Option<String>,
+    },
+    SetPublishRate(u64),
+}
+
+#[derive(Clone)]
+pub struct TimedAction {
+    pub time: Duration,
+    pub action: Action,
+}
+
+/// Timeline of actions read from a text file with one action per line:
+///
+/// ```text
+/// # comment
+/// t=2s create writer Square BLUE reliable
+/// t=3s create reader Square
+/// t=10s delete writer Square BLUE
+/// t=15s change rate to 200ms
+/// t=20s delete reader
+/// ```
+///
+/// Deleting without naming the topic (and color) deletes the most recently
+/// created writer or reader.
+#[derive(Clone)]
+pub struct Timeline {
+    actions: Vec<TimedAction>,
+}
+
+impl Timeline {
+    pub fn from_file(path: &str) -> Result<Self, String> {
+        let text = std::fs::read_to_string(path)
+            .map_err(|e| format!("Couldn't read timeline file {}: {}", path, e))?;
+        Self::parse(&text)
+    }
+
+    pub fn parse(text: &str) -> Result<Self, String> {
+        let mut actions = Vec::new();
+        for (line_number, line) in text.lines().enumerate() {
+            let line = line.split('#').next().unwrap_or_default().trim();
+            if line.is_empty() {
+                continue;
+            }
+            let action = parse_line(line)
+                .map_err(|e| format!("Line {}: {}: \"{}\"", line_number + 1, e, line))?;
+            actions.push(action);
+        }
+        actions.sort_by_key(|a| a.time);
+        Ok(Self { actions })
+    }
+}
+
+fn parse_duration(text: &str) -> Result<Duration, String> {
+    let invalid = || format!("Invalid time \"{}\"", text);
+    if let Some(ms) = text.strip_suffix("ms") {
+        Ok(Duration::from_millis(ms.parse().map_err(|_| invalid())?))
+    } else if let Some(s) = text.strip_suffix('s') {
+        Duration::try_from_secs_f64(s.parse().map_err(|_| invalid())?).map_err(|_| invalid())
+    } else {
+        Err(invalid())
+    }
+}
+
+fn parse_reliability(word: Option<&str>) -> Result<bool, String> {
+    match word {
+        None | Some("best_effort") => Ok(false),
+        Some("reliable") => Ok(true),
+        Some(word) => Err(format!("Invalid reliability \"{}\"", word)),
+    }
+}
+
+fn parse_line(line: &str) -> Result<TimedAction, String> {
+    let words: Vec<&str> = line.split_whitespace().collect();
+    let time = words
+        .first()
+        .and_then(|word| word.strip_prefix("t="))
+        .ok_or("Missing time")?;
+    let time = parse_duration(time)?;
+    let action = match words[1..] {
+        ["create", "writer", topic_name, color] | ["create", "writer", topic_name, color, _] => {
+            Action::CreateWriter(WriterSettings {
+                topic_name: topic_name.to_string(),
+                color: color.to_string(),
+                is_reliable: parse_reliability(words.get(5).copied())?,
+                ..Default::default()
+            })
+        }
+        ["create", "reader", topic_name] | ["create", "reader", topic_name, _] => {
+            Action::CreateReader(ReaderSettings {
+                topic_name: topic_name.to_string(),
+                is_reliable: parse_reliability(words.get(4).copied())?,
+                ..Default::default()
+            })
+        }
+        ["delete", "writer"] => Action::DeleteWriter {
+            topic_name: None,
+            color: None,
+        },
+        ["delete", "writer", topic_name, color] => Action::DeleteWriter {
+            topic_name: Some(topic_name.to_string()),
+            color: Some(color.to_string()),
+        },
+        ["delete", "reader"] => Action::DeleteReader { topic_name: None },
+        ["delete", "reader", topic_name] => Action::DeleteReader {
+            topic_name: Some(topic_name.to_string()),
+        },
+        ["change", "rate", "to", rate] => {
+            let rate = parse_duration(rate)?;
+            Action::SetPublishRate(rate.as_millis() as u64)
+        }
+        _ => return Err("Unknown action".to_string()),
+    };
+    Ok(TimedAction { time, action })
+}
+
+pub struct TimelineRunner {
+    timeline: Timeline,
+    start: Instant,
+    next_action: usize,
+}
+
+impl TimelineRunner {
+    pub fn new(timeline: Timeline) -> Self {
+        Self {
+            timeline,
+            start: Instant::now(),
+            next_action: 0,
+        }
+    }
+
+    /// Returns the actions that became due since the last call, in order.
+    pub fn due_actions(&mut self) -> Vec<Action> {
+        let elapsed = self.start.elapsed();
+        let mut due_actions = Vec::new();
+        while let Some(timed_action) = self.timeline.actions.get(self.next_action) {
+            if timed_action.time > elapsed {
+                break;
+            }
+            due_actions.push(timed_action.action.clone());
+            self.next_action += 1;
+        }
+        due_actions
+    }
+
+    /// Time left until the next action is due, if any.
+    pub fn time_to_next_action(&self) -> Option<Duration> {
+        let timed_action = self.timeline.actions.get(self.next_action)?;
+        Some(timed_action.time.saturating_sub(self.start.elapsed()))
+    }
+
+    pub fn executed_actions(&self) -> usize {
+        self.next_action
+    }
+
+    pub fn total_actions(&self) -> usize {
+        self.timeline.actions.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_actions_sorted_by_time() {
+        let timeline = Timeline::parse(
+            "# comment\n\
+             t=10s delete writer Square BLUE\n\
+             t=2s create writer Square BLUE reliable # trailing comment\n\
+             \n\
+             t=1500ms create reader Circle\n\
+             t=15s change rate to 200ms\n\
+             t=20s delete reader\n",
+        )
+        .unwrap();
+        let times: Vec<_> = timeline.actions.iter().map(|a| a.time).collect();
+        assert_eq!(
+            times,
+            [
+                Duration::from_millis(1500),
+                Duration::from_secs(2),
+                Duration::from_secs(10),
+                Duration::from_secs(15),
+                Duration::from_secs(20),
+            ]
+        );
+        assert!(matches!(
+            &timeline.actions[0].action,
+            Action::CreateReader(settings) if settings.topic_name == "Circle" && !settings.is_reliable
+        ));
+        assert!(matches!(
+            &timeline.actions[1].action,
+            Action::CreateWriter(settings)
+                if settings.topic_name == "Square" && settings.color == "BLUE" && settings.is_reliable
+        ));
+        assert!(matches!(
+            &timeline.actions[2].action,
+            Action::DeleteWriter { topic_name: Some(topic_name), color: Some(color) }
+                if topic_name == "Square" && color == "BLUE"
+        ));
+        assert!(matches!(
+            timeline.actions[3].action,
+            Action::SetPublishRate(200)
+        ));
+        assert!(matches!(
+            timeline.actions[4].action,
+            Action::DeleteReader { topic_name: None }
+        ));
+    }
+
+    #[test]
+    fn parse_invalid_times() {
+        for line in [
+            "t=-1s delete reader",
+            "t=NaNs delete reader",
+            "t=infs delete reader",
+            "t=1e30s delete reader",
+            "t=2 delete reader",
+            "delete reader",
+        ] {
+            assert!(Timeline::parse(line).is_err(), "{}", line);
+        }
+    }
+
+    #[test]
+    fn parse_error_reports_line() {
+        let Err(error) = Timeline::parse("t=1s delete reader\nt=2s fly away") else {
+            panic!("the invalid action was parsed");
+        };
+        assert!(error.starts_with("Line 2: Unknown action"), "{}", error);
+    }
+
+    #[test]
+    fn parse_invalid_reliability() {
+        assert!(Timeline::parse("t=1s create reader Square fast").is_err());
+    }
+}
@@ -0,0 +1,116 @@
+use std::collections::HashMap;
+
+use dust_dds::{
+    dds_async::{domain_participant::DomainParticipantAsync, topic::TopicAsync},
+    infrastructure::{
+        error::{DdsError, DdsResult},
+        listeners::NoOpListener,
+        qos::{QosKind, TopicQos},
+        status::NO_STATUS,
+        time::Duration,
+    },
+};
+
+use super::app::shapes_type::ShapeType;
+
+const TYPE_NAME: &str = "ShapeType";
+
+/// Time spent looking for an existing topic before creating it. Topics of the
+/// participant and discovered topics are found on the first attempt.
+const FIND_TOPIC_TIMEOUT: Duration = Duration::new(0, 1_000_000);
+
+/// Topics of the participant, shared by all the writers and readers of the
+/// same topic name instead of creating a topic for each endpoint.
+#[derive(Default)]
+pub struct TopicRegistry {
+    topics: HashMap<String, TopicAsync>,
+    qos: TopicQos,
+}
+
+impl TopicRegistry {
+    pub fn qos(&self) -> &TopicQos {
+        &self.qos
+    }
+
+    /// Sets the QoS of the topics created from now on. Only the topic data of
+    /// the existing topics is changed since their other policies are immutable
+    /// once enabled.
+    pub async fn set_qos(&mut self, qos: TopicQos) -> DdsResult<()> {
+        for topic in self.topics.values() {
+            let mut topic_qos = topic.get_qos().await?;
+            topic_qos.topic_data = qos.topic_data.clone();
+            topic.set_qos(QosKind::Specific(topic_qos)).await?;
+        }
+        self.qos = qos;
+        Ok(())
+    }
+
+    /// Returns the topic with the given name, finding it in the participant
+    /// or among the discovered topics, or creating it if it doesn't exist yet.
+    pub async fn get_or_create(
+        &mut self,
+        participant: &DomainParticipantAsync,
+        topic_name: &str,
+    ) -> DdsResult<TopicAsync> {
+        if let Some(topic) = self.topics.get(topic_name) {
+            return Ok(topic.clone());
+        }
+        let topic = match participant
+            .find_topic::<ShapeType>(topic_name, FIND_TOPIC_TIMEOUT)
+            .await
+        {
+            // A discovered topic is created locally with the type name of the
+            // remote topic, so it is deleted again if that type is different
+            Ok(topic) if topic.get_type_name() != TYPE_NAME => {
+                participant.delete_topic(&topic).await.ok();
+                return Err(DdsError::PreconditionNotMet(format!(
+                    "Topic {} already exists with type {}",
+                    topic_name,
+                    topic.get_type_name()
+                )));
+            }
+            Ok(topic) => topic,
+            Err(DdsError::Timeout) => {
+                participant
+                    .create_topic::<ShapeType>(
+                        topic_name,
+                        TYPE_NAME,
+                        QosKind::Specific(self.qos.clone()),
+                        NoOpListener::new(),
+                        NO_STATUS,
+                    )
+                    .await?
+            }
+            Err(e) => return Err(e),
+        };
+        self.topics.insert(topic_name.to_string(), topic.clone());
+        Ok(topic)
+    }
+
+    pub async fn delete(
+        &mut self,
+        participant: &DomainParticipantAsync,
+        topic_name: &str,
+    ) -> DdsResult<()> {
+        match self.topics.remove(topic_name) {
+            Some(topic) => participant.delete_topic(&topic).await,
+            None => Ok(()),
+        }
+    }
+
+    /// Names of the topics for which remote topics with the same name but a
+    /// different type were discovered since the last call.
+    pub async fn inconsistent_topics(&self) -> Vec<String> {
+        let mut inconsistent_topics = Vec::new();
+        for (topic_name, topic) in self.topics.iter() {
+            if topic
+                .get_inconsistent_topic_status()
+                .await
+                .is_ok_and(|status| status.total_count_change > 0)
+            {
+                inconsistent_topics.push(topic_name.clone());
+            }
+        }
+        inconsistent_topics
+    }
+}